@@ -1,13 +1,18 @@
 mod day_01;
 
-pub fn run_day_number(day: u32, input: &Vec<&str>) {
-  match day {
-    1 => {
-      let frequency = day_01::find_frequency(input);
-      println!("\nDay 01:");
-      println!("  Result Frequency:    {:?}", frequency);
-    }
-
-    _ => println!("Code for day {:?} undefined", day),
-  }
-}
\ No newline at end of file
+use crate::runner::Part;
+
+pub fn day_parts(day: u32) -> Option<Vec<Part>> {
+  let parts = match day {
+    1 => vec![
+      Part {
+        label: "Result frequency",
+        solve: |input| day_01::find_frequency(input).to_string(),
+      },
+    ],
+
+    _ => return None,
+  };
+
+  Some(parts)
+}
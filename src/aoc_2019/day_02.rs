@@ -0,0 +1,43 @@
+use super::intcode::Intcode;
+
+/// The output part 2 is looking for.
+const TARGET_OUTPUT: i64 = 19690720;
+
+/// Day 2, Part 1 -- https://adventofcode.com/2019/day/2
+///
+/// The gravity assist program on the way to Venus has crashed, and it's
+/// written in Intcode: a list of integers where opcode 1 adds the values
+/// at the next two positions and stores the result at the third, opcode
+/// 2 multiplies instead, and opcode 99 halts. After each instruction you
+/// move forward 4 positions.
+///
+/// Before running it, put the program back into its "1202 program alarm"
+/// state by setting position 1 to 12 and position 2 to 2. What value is
+/// left at position 0 after the program halts?
+pub fn find_alarm_state_output(input: &Vec<&str>) -> i64 {
+  run_with_inputs(&Intcode::from_input(input), 12, 2)
+}
+
+/// Day 2, Part 2
+///
+/// Positions 1 and 2 are actually the inputs to the program, a "noun"
+/// and a "verb", each somewhere between 0 and 99. Find the noun and verb
+/// that make the program leave 19690720 at position 0. What is
+/// 100 * noun + verb?
+pub fn find_noun_verb(input: &Vec<&str>) -> i64 {
+  let program = Intcode::from_input(input);
+  (0..=99)
+    .flat_map(|noun| (0..=99).map(move |verb| (noun, verb)))
+    .find(|&(noun, verb)| run_with_inputs(&program, noun, verb) == TARGET_OUTPUT)
+    .map(|(noun, verb)| 100 * noun + verb)
+    .expect("No noun and verb produce the target output")
+}
+
+// Helpers
+fn run_with_inputs(program: &Intcode, noun: i64, verb: i64) -> i64 {
+  let mut machine = program.clone();
+  machine.write(1, noun);
+  machine.write(2, verb);
+  machine.run();
+  machine.read(0)
+}
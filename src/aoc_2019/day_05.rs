@@ -0,0 +1,40 @@
+use super::intcode::Intcode;
+
+/// Day 5, Part 1 -- https://adventofcode.com/2019/day/5
+///
+/// The ship's air conditioner needs its Thermal Environment Supervision
+/// Terminal (TEST) diagnostic run, which means the Intcode computer needs
+/// two new instructions: opcode 3 reads an input into a position and
+/// opcode 4 outputs a value. Parameters also get modes now, set by the
+/// digits above the opcode: 0 reads from a position, 1 uses the value
+/// directly.
+///
+/// Run the diagnostic with the ID of the air conditioner unit, 1. Every
+/// output but the last should be 0 (a passing test); the last is the
+/// diagnostic code. What is it?
+pub fn find_air_conditioner_diagnostic(input: &Vec<&str>) -> i64 {
+  run_diagnostic(input, 1)
+}
+
+/// Day 5, Part 2
+///
+/// The thermal radiator controller needs jumps and comparisons too:
+/// opcodes 5 and 6 jump if their first parameter is non-zero or zero,
+/// opcode 7 stores 1 if the first parameter is less than the second and
+/// opcode 8 stores 1 if they're equal, otherwise both store 0.
+///
+/// Run the diagnostic with the ID of the thermal radiator controller, 5.
+/// What is the diagnostic code?
+pub fn find_radiator_diagnostic(input: &Vec<&str>) -> i64 {
+  run_diagnostic(input, 5)
+}
+
+// Helpers
+fn run_diagnostic(input: &Vec<&str>, system_id: i64) -> i64 {
+  let output = Intcode::from_input(input).run_with(&[system_id]);
+  let (code, tests) = output.split_last().expect("Expected the diagnostic to output something");
+  if let Some(failed) = tests.iter().position(|&test| test != 0) {
+    panic!("Diagnostic test {} failed with {}", failed, tests[failed]);
+  }
+  *code
+}
@@ -0,0 +1,65 @@
+use itertools::Itertools;
+
+use super::intcode::{run_chain, Intcode};
+
+/// Day 7, Part 1 -- https://adventofcode.com/2019/day/7
+///
+/// To get to Santa in time you need more thrust, which comes from a
+/// series of five amplifiers all running the same Intcode program. Each
+/// amplifier first reads its phase setting (0 to 4, each used exactly
+/// once), then an input signal, and outputs a signal for the next
+/// amplifier. The first amplifier gets 0, and the last one's output goes
+/// to the thrusters.
+///
+/// What is the highest signal that can be sent to the thrusters?
+pub fn find_max_thruster_signal(input: &Vec<&str>) -> i64 {
+  max_signal(&Intcode::from_input(input), 0..5, false)
+}
+
+/// Day 7, Part 2
+///
+/// The amplifiers need to be wired into a feedback loop, with the last
+/// amplifier's output going back into the first. Phases are now 5 to 9
+/// and the amplifiers keep passing signals around the loop until they
+/// all halt. The last signal out of the final amplifier goes to the
+/// thrusters.
+///
+/// What is the highest signal that can be sent to the thrusters?
+pub fn find_max_feedback_signal(input: &Vec<&str>) -> i64 {
+  max_signal(&Intcode::from_input(input), 5..10, true)
+}
+
+// Helpers
+fn max_signal(program: &Intcode, phases: std::ops::Range<i64>, feedback: bool) -> i64 {
+  phases
+    .permutations(5)
+    .map(|order| {
+      let mut amplifiers = order
+        .iter()
+        .map(|&phase| {
+          let mut amplifier = program.clone();
+          amplifier.push_input(phase);
+          amplifier
+        })
+        .collect_vec();
+      run_chain(&mut amplifiers, 0, feedback)
+    })
+    .max()
+    .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn example_amplifiers() {
+    let program = Intcode::parse("3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0");
+    assert_eq!(max_signal(&program, 0..5, false), 43210);
+
+    let program = Intcode::parse(
+      "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5",
+    );
+    assert_eq!(max_signal(&program, 5..10, true), 139629729);
+  }
+}
@@ -0,0 +1,31 @@
+use super::intcode::Intcode;
+
+/// Day 9, Part 1 -- https://adventofcode.com/2019/day/9
+///
+/// A distress signal from Ceres needs the Intcode computer upgraded one
+/// last time: parameters can now be in relative mode (2), read from the
+/// relative base plus the parameter, and opcode 9 adjusts the relative
+/// base. Memory past the end of the program starts at 0 and is fair game,
+/// and values can be very large.
+///
+/// The BOOST program tests all of this. Run it in test mode by giving it
+/// the input 1; it should output only the BOOST keycode. What is it?
+pub fn find_boost_keycode(input: &Vec<&str>) -> i64 {
+  run_boost(input, 1)
+}
+
+/// Day 9, Part 2
+///
+/// Run the BOOST program in sensor boost mode by giving it the input 2.
+/// What are the coordinates of the distress signal?
+pub fn find_distress_coordinates(input: &Vec<&str>) -> i64 {
+  run_boost(input, 2)
+}
+
+// Helpers
+fn run_boost(input: &Vec<&str>, mode: i64) -> i64 {
+  match Intcode::from_input(input).run_with(&[mode])[..] {
+    [value] => value,
+    ref output => panic!("BOOST reported failing opcodes: {:?}", output),
+  }
+}
@@ -0,0 +1,285 @@
+#![allow(dead_code)]
+
+use std::collections::VecDeque;
+
+/// Why a machine stopped running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum State {
+  /// Hit an input instruction with nothing queued. Push some input and
+  /// call `run` again to carry on from the same instruction.
+  AwaitingInput,
+  Halted,
+}
+
+/// An Intcode computer. Memory grows (zero filled) as soon as anything
+/// past the end is touched, and input/output are queues so machines can
+/// be wired together and run a bit at a time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Intcode {
+  memory: Vec<i64>,
+  ip: usize,
+  relative_base: i64,
+  input: VecDeque<i64>,
+  output: VecDeque<i64>,
+  state: Option<State>,
+}
+
+impl Intcode {
+  pub fn new(program: Vec<i64>) -> Intcode {
+    Intcode {
+      memory: program,
+      ip: 0,
+      relative_base: 0,
+      input: VecDeque::new(),
+      output: VecDeque::new(),
+      state: None,
+    }
+  }
+
+  /// Loads a comma separated program, e.g. `1,0,0,3,99`.
+  pub fn parse(program: &str) -> Intcode {
+    Intcode::new(
+      program
+        .trim()
+        .split(',')
+        .map(|value| value.trim().parse().unwrap_or_else(|_| panic!("Invalid Intcode value: '{}'", value)))
+        .collect(),
+    )
+  }
+
+  /// Loads the program from the first non-blank line of a day's input.
+  pub fn from_input(input: &[&str]) -> Intcode {
+    Intcode::parse(input.iter().find(|line| !line.is_empty()).expect("Expected an Intcode program"))
+  }
+
+  pub fn read(&self, address: usize) -> i64 {
+    self.memory.get(address).copied().unwrap_or(0)
+  }
+
+  pub fn write(&mut self, address: usize, value: i64) {
+    if address >= self.memory.len() {
+      self.memory.resize(address + 1, 0);
+    }
+    self.memory[address] = value;
+  }
+
+  pub fn push_input(&mut self, value: i64) {
+    self.input.push_back(value);
+  }
+
+  /// The oldest output not yet taken.
+  pub fn pop_output(&mut self) -> Option<i64> {
+    self.output.pop_front()
+  }
+
+  /// Every output not yet taken, oldest first.
+  pub fn take_output(&mut self) -> Vec<i64> {
+    self.output.drain(..).collect()
+  }
+
+  /// Where the machine last stopped, or `None` if it hasn't run yet.
+  pub fn state(&self) -> Option<State> {
+    self.state
+  }
+
+  pub fn is_halted(&self) -> bool {
+    self.state == Some(State::Halted)
+  }
+
+  /// Runs until the program halts or needs input that isn't queued.
+  pub fn run(&mut self) -> State {
+    let state = loop {
+      if let Some(state) = self.step() {
+        break state;
+      }
+    };
+    self.state = Some(state);
+    state
+  }
+
+  /// Queues `input`, runs, and hands back everything that was output.
+  pub fn run_with(&mut self, input: &[i64]) -> Vec<i64> {
+    self.input.extend(input);
+    self.run();
+    self.take_output()
+  }
+
+  /// Runs until one value is output, for machines that talk back and
+  /// forth. `None` if it halts or blocks on input first.
+  pub fn next_output(&mut self) -> Option<i64> {
+    while self.output.is_empty() {
+      if let Some(state) = self.step() {
+        self.state = Some(state);
+        break;
+      }
+    }
+    self.output.pop_front()
+  }
+
+  // Helpers
+  // Executes one instruction. Returns the state if it stopped the machine.
+  fn step(&mut self) -> Option<State> {
+    let instruction = self.read(self.ip);
+    match instruction % 100 {
+      1 => {
+        let value = self.param(instruction, 1) + self.param(instruction, 2);
+        self.store(instruction, 3, value);
+        self.ip += 4;
+      }
+      2 => {
+        let value = self.param(instruction, 1) * self.param(instruction, 2);
+        self.store(instruction, 3, value);
+        self.ip += 4;
+      }
+      3 => {
+        // Leave the ip on this instruction so it's retried once input arrives
+        let Some(value) = self.input.pop_front() else {
+          return Some(State::AwaitingInput);
+        };
+        self.store(instruction, 1, value);
+        self.ip += 2;
+      }
+      4 => {
+        let value = self.param(instruction, 1);
+        self.output.push_back(value);
+        self.ip += 2;
+      }
+      5 => match self.param(instruction, 1) != 0 {
+        true => self.ip = self.address(self.param(instruction, 2)),
+        false => self.ip += 3,
+      },
+      6 => match self.param(instruction, 1) == 0 {
+        true => self.ip = self.address(self.param(instruction, 2)),
+        false => self.ip += 3,
+      },
+      7 => {
+        let value = (self.param(instruction, 1) < self.param(instruction, 2)) as i64;
+        self.store(instruction, 3, value);
+        self.ip += 4;
+      }
+      8 => {
+        let value = (self.param(instruction, 1) == self.param(instruction, 2)) as i64;
+        self.store(instruction, 3, value);
+        self.ip += 4;
+      }
+      9 => {
+        self.relative_base += self.param(instruction, 1);
+        self.ip += 2;
+      }
+      99 => return Some(State::Halted),
+      _ => panic!("Invalid opcode {} at {}", instruction, self.ip),
+    }
+    None
+  }
+
+  // The value of the `n`th parameter (1 based) of the current instruction
+  fn param(&self, instruction: i64, n: usize) -> i64 {
+    let raw = self.read(self.ip + n);
+    match mode(instruction, n) {
+      0 => self.read(self.address(raw)),
+      1 => raw,
+      2 => self.read(self.address(self.relative_base + raw)),
+      other => panic!("Invalid parameter mode {} in {}", other, instruction),
+    }
+  }
+
+  // Writes to the address given by the `n`th parameter
+  fn store(&mut self, instruction: i64, n: usize, value: i64) {
+    let raw = self.read(self.ip + n);
+    let address = match mode(instruction, n) {
+      0 => self.address(raw),
+      2 => self.address(self.relative_base + raw),
+      other => panic!("Invalid write parameter mode {} in {}", other, instruction),
+    };
+    self.write(address, value);
+  }
+
+  fn address(&self, value: i64) -> usize {
+    usize::try_from(value).unwrap_or_else(|_| panic!("Negative address {} at {}", value, self.ip))
+  }
+}
+
+/// Feeds `input` into the first machine and each machine's output into the
+/// next, returning the last machine's final output. With `feedback` the
+/// last machine's output goes back into the first until they all halt.
+/// Any setup input (like amplifier phases) should already be queued.
+///
+/// Panics if a feedback loop stalls, i.e. a pass gets nothing out of the
+/// last machine while some machines are still waiting for input.
+pub fn run_chain(machines: &mut [Intcode], input: i64, feedback: bool) -> i64 {
+  let mut signal = vec![input];
+  let mut last = None;
+  loop {
+    for machine in machines.iter_mut() {
+      signal = machine.run_with(&signal);
+    }
+    last = signal.last().copied().or(last);
+    if !feedback || machines.iter().all(Intcode::is_halted) {
+      break;
+    }
+    // Nothing goes back into the first machine, so no machine can move again
+    if signal.is_empty() {
+      panic!("Feedback loop stalled with every running machine waiting for input");
+    }
+  }
+  last.expect("Expected the chain to output something")
+}
+
+// Helpers
+fn mode(instruction: i64, n: usize) -> i64 {
+  instruction / 10_i64.pow(n as u32 + 1) % 10
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn positional_and_immediate_modes() {
+    let mut machine = Intcode::parse("1,9,10,3,2,3,11,0,99,30,40,50");
+    assert_eq!(machine.run(), State::Halted);
+    assert_eq!(machine.read(0), 3500);
+
+    let mut machine = Intcode::parse("1002,4,3,4,33");
+    machine.run();
+    assert_eq!(machine.read(4), 99);
+  }
+
+  #[test]
+  fn jumps_and_comparisons() {
+    // Outputs 999 below 8, 1000 at 8 and 1001 above
+    let program = "3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,1106,0,36,98,0,0,1002,21,125,20,4,20,1105,1,\
+                   46,104,999,1105,1,46,1101,1000,1,20,4,20,1105,1,46,98,99";
+    for (input, expected) in [(7, 999), (8, 1000), (9, 1001)] {
+      assert_eq!(Intcode::parse(program).run_with(&[input]), vec![expected]);
+    }
+  }
+
+  #[test]
+  fn relative_mode_and_growing_memory() {
+    let quine = "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99";
+    let expected: Vec<i64> = quine.split(',').map(|v| v.parse().unwrap()).collect();
+    assert_eq!(Intcode::parse(quine).run_with(&[]), expected);
+    assert_eq!(Intcode::parse("104,1125899906842624,99").run_with(&[]), vec![1125899906842624]);
+  }
+
+  #[test]
+  fn pauses_for_input() {
+    let mut machine = Intcode::parse("3,0,4,0,3,0,4,0,99");
+    assert_eq!(machine.run(), State::AwaitingInput);
+    machine.push_input(5);
+    assert_eq!(machine.next_output(), Some(5));
+    assert_eq!(machine.next_output(), None);
+    assert_eq!(machine.state(), Some(State::AwaitingInput));
+    assert_eq!(machine.run_with(&[6]), vec![6]);
+    assert!(machine.is_halted());
+  }
+
+  #[test]
+  #[should_panic(expected = "Feedback loop stalled")]
+  fn stalled_feedback_loop_panics() {
+    // Reads two inputs before ever writing anything
+    let mut machines = vec![Intcode::parse("3,0,3,0,4,0,99"), Intcode::parse("3,0,4,0,99")];
+    run_chain(&mut machines, 1, true);
+  }
+}
@@ -1,15 +1,67 @@
 mod day_01;
+mod day_02;
+mod day_05;
+mod day_07;
+mod day_09;
+mod intcode;
 
-pub fn run_day_number(day: u32, input: &Vec<&str>) {
-  match day {
-    1 => {
-      let fuel_sum = day_01::find_fuel_requirements(input);
-      let recursive_fuel_sum = day_01::find_fuel_requirements_rec(input);
-      println!("\nDay 01:");
-      println!("  Fuel Requirement Sum:    {:?}", fuel_sum);
-      println!("  Fuel Requirement Sum:    {:?}", recursive_fuel_sum);
-    }
-
-    _ => println!("Code for day {:?} undefined", day),
-  }
+use crate::runner::Part;
+
+pub fn day_parts(day: u32) -> Option<Vec<Part>> {
+  let parts = match day {
+    1 => vec![
+      Part {
+        label: "Fuel requirement sum",
+        solve: |input| day_01::find_fuel_requirements(input).to_string(),
+      },
+      Part {
+        label: "Recursive fuel requirement sum",
+        solve: |input| day_01::find_fuel_requirements_rec(input).to_string(),
+      },
+    ],
+    2 => vec![
+      Part {
+        label: "Position 0 in 1202 alarm state",
+        solve: |input| day_02::find_alarm_state_output(input).to_string(),
+      },
+      Part {
+        label: "Noun and verb for 19690720",
+        solve: |input| day_02::find_noun_verb(input).to_string(),
+      },
+    ],
+    5 => vec![
+      Part {
+        label: "Air conditioner diagnostic code",
+        solve: |input| day_05::find_air_conditioner_diagnostic(input).to_string(),
+      },
+      Part {
+        label: "Thermal radiator diagnostic code",
+        solve: |input| day_05::find_radiator_diagnostic(input).to_string(),
+      },
+    ],
+    7 => vec![
+      Part {
+        label: "Highest thruster signal",
+        solve: |input| day_07::find_max_thruster_signal(input).to_string(),
+      },
+      Part {
+        label: "Highest feedback loop signal",
+        solve: |input| day_07::find_max_feedback_signal(input).to_string(),
+      },
+    ],
+    9 => vec![
+      Part {
+        label: "BOOST keycode",
+        solve: |input| day_09::find_boost_keycode(input).to_string(),
+      },
+      Part {
+        label: "Distress signal coordinates",
+        solve: |input| day_09::find_distress_coordinates(input).to_string(),
+      },
+    ],
+
+    _ => return None,
+  };
+
+  Some(parts)
 }
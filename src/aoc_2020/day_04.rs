@@ -1,5 +1,7 @@
 #![allow(dead_code)]
 
+use crate::utils::parse;
+
 /// Day 4, Part 1 -- https://adventofcode.com/2020/day/4
 ///
 /// You made it to the airport! However, you just realized that
@@ -45,33 +47,17 @@ pub fn count_passports_validated_by_field(logs_input: &Vec<&str>) -> usize {
 }
 
 fn generate_passports_from_dump(log_dump: &Vec<&str>) -> Vec<Passport> {
-  let mut passport_dumps_collapsed: Vec<String> = vec![];
-  let mut temp_passport = "".to_owned();
-  for log in log_dump {
-    if log.trim() == "" {
-      passport_dumps_collapsed.push(temp_passport);
-      temp_passport = "".to_owned();
-      continue;
-    } else {
-      if temp_passport == "" {
-        temp_passport = log.trim().to_owned().to_owned();
-      } else {
-        temp_passport = format!("{} {}", temp_passport, log.trim());
-      }
-    }
-  }
-  passport_dumps_collapsed.push(temp_passport);
-
-  return passport_dumps_collapsed
-    .into_iter()
-    .map(|collapsed_passport_text| {
+  parse::blocks(log_dump)
+    .iter()
+    .map(|block| {
       let mut passport = Passport::new();
-      for property in collapsed_passport_text.split(" ") {
-        passport.add_property(property)
+      let fields = parse::record(block).unwrap_or_else(|e| panic!("Invalid passport dump: {}", e));
+      for (key, value) in fields {
+        passport.add_property(key, value)
       }
-      return passport;
+      passport
     })
-    .collect();
+    .collect()
 }
 
 pub struct Passport {
@@ -124,10 +110,9 @@ impl Passport {
     .all(|b| *b);
   }
 
-  pub fn add_property(&mut self, new_property: &str) {
-    let key_value_pair = new_property.split(":").collect::<Vec<&str>>();
-    let value = key_value_pair[1].to_owned();
-    match key_value_pair[0] {
+  pub fn add_property(&mut self, key: &str, value: &str) {
+    let value = value.to_owned();
+    match key {
       "byr" => self.byr = Some(value),
       "iyr" => self.iyr = Some(value),
       "eyr" => self.eyr = Some(value),
@@ -136,7 +121,7 @@ impl Passport {
       "ecl" => self.ecl = Some(value),
       "pid" => self.pid = Some(value),
       "cid" => self.cid = Some(value),
-      _ => panic!("Invalid property passed! [{}]", new_property),
+      _ => panic!("Invalid property passed! [{}:{}]", key, value),
     }
   }
 
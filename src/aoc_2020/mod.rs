@@ -3,39 +3,52 @@ mod day_02;
 mod day_03;
 mod day_04;
 
-pub fn run_day_number(day: u32, input: &Vec<&str>) {
-  match day {
-    1 => {
-      let two_sum_product = day_01::find_2_sum_product(input);
-      let three_sum_product = day_01::find_3_sum_product(input);
-      println!("\nDay 1!");
-      println!("  Expense report 2-sum product:    {:?}", two_sum_product);
-      println!("  Expense report 3-sum product:    {:?}", three_sum_product);
-    }
+use crate::runner::Part;
 
-    2 => {
-      let valid_by_count = day_02::find_valid_passwords_by_count(input);
-      let valid_by_position = day_02::find_valid_passwords_by_position(input);
+pub fn day_parts(day: u32) -> Option<Vec<Part>> {
+  let parts = match day {
+    1 => vec![
+      Part {
+        label: "Expense report 2-sum product",
+        solve: |input| day_01::find_2_sum_product(input).to_string(),
+      },
+      Part {
+        label: "Expense report 3-sum product",
+        solve: |input| day_01::find_3_sum_product(input).to_string(),
+      },
+    ],
 
-      println!("\n Day 2!");
-      println!("   Valid passwords by char count: {:?}", valid_by_count);
-      println!("   Valid passwords by char pos:   {:?}", valid_by_position);
-    }
+    2 => vec![
+      Part {
+        label: "Valid passwords by char count",
+        solve: |input| day_02::find_valid_passwords_by_count(input).to_string(),
+      },
+      Part {
+        label: "Valid passwords by char pos",
+        solve: |input| day_02::find_valid_passwords_by_position(input).to_string(),
+      },
+    ],
 
-    3 => {
-      let default_collisions = day_03::count_tree_collisions(input);
-      let collision_product = day_03::count_tree_collision_product(input);
-      println!("\n Day 3!");
-      println!("  Straight line colisions:     {:?}", default_collisions);
-      println!("  Multiple collisions product: {:?}", collision_product);
-    }
+    3 => vec![
+      Part {
+        label: "Straight line collisions",
+        solve: |input| day_03::count_tree_collisions(input).to_string(),
+      },
+      Part {
+        label: "Multiple collisions product",
+        solve: |input| day_03::count_tree_collision_product(input).to_string(),
+      },
+    ],
 
-    4 => {
-      let valid_passports = day_04::count_valid_passports(input);
-      println!("\n Day 4!");
-      println!("  Valid passport count: {:?}", valid_passports);
-    }
+    4 => vec![
+      Part {
+        label: "Valid passport count",
+        solve: |input| day_04::count_valid_passports(input).to_string(),
+      },
+    ],
 
-    _ => println!("Code for day {:?} undefined", day),
-  }
+    _ => return None,
+  };
+
+  Some(parts)
 }
@@ -13,7 +13,7 @@ use std::collections::HashMap;
 /// Win      +6   Scissors +3
 ///
 /// Calculate how many points you should end up with at the end tournament.
-pub fn calculate_total_score(encrypted_strategy_guide: &Vec<&str>) {
+pub fn calculate_total_score(encrypted_strategy_guide: &Vec<&str>) -> i32 {
   let mut points = 0;
   let match_dict = build_match_dict();
   encrypted_strategy_guide.iter().for_each(|encrypted_match| {
@@ -25,7 +25,7 @@ pub fn calculate_total_score(encrypted_strategy_guide: &Vec<&str>) {
     points += get_points_for_choice(choices[1]);
   });
 
-  println!("Points gathered: {points}");
+  return points;
 }
 
 /// Day 2 Part 2 -- https://adventofcode.com/2022/day/2#part2
@@ -33,7 +33,7 @@ pub fn calculate_total_score(encrypted_strategy_guide: &Vec<&str>) {
 /// Same basic pitch for the rock/paper/scissors tournament, but
 /// instead of the 2nd input being what you should throw, it's instead
 /// whether you should win/tie/lose the match.
-pub fn calculate_total_score_adjusted(encrypted_strategy_guide: &Vec<&str>) {
+pub fn calculate_total_score_adjusted(encrypted_strategy_guide: &Vec<&str>) -> i32 {
   let mut points = 0;
   let match_dict = build_decision_dict();
   encrypted_strategy_guide.iter().for_each(|encrypted_match| {
@@ -42,7 +42,7 @@ pub fn calculate_total_score_adjusted(encrypted_strategy_guide: &Vec<&str>) {
     }
   });
 
-  println!("Points gathered: {points}");
+  return points;
 }
 
 /// Helper function that builds the lookup table for Part 1
@@ -30,7 +30,7 @@ pub fn characters_before_start_of_packet(signal_string: &Vec<&str>) -> usize {
 /// Day 6 Part 2 -- https://adventofcode.com/2022/day/6#part2
 ///
 /// Same as above, except for a 14 character entry instead of 4
-pub fn characters_before_start_of_packet_big(signal_string: &Vec<&str>) {
+pub fn characters_before_start_of_packet_big(signal_string: &Vec<&str>) -> usize {
   let signal = String::from(signal_string[0]);
   for i in 0..signal.len() - 13 {
     let mut set: HashSet<&char> = HashSet::new();
@@ -45,7 +45,7 @@ pub fn characters_before_start_of_packet_big(signal_string: &Vec<&str>) {
         i + 14,
         slice
       );
-      return;
+      return i + 14;
     }
   }
   panic!("Signal init not found!");
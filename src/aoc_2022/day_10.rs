@@ -1,5 +1,13 @@
 #![allow(dead_code)]
 
+use std::ops::RangeInclusive;
+
+use crate::utils::{
+  cpu::{self, Cpu, Observer, Tick},
+  ocr,
+};
+
+const CRT_WIDTH: i64 = 40;
 
 /// Day 10 Part 1 -- https://adventofcode.com/2022/day/10
 ///
@@ -15,37 +23,10 @@
 /// - Takes one cycle to complete
 /// - No operation enacted
 ///
-pub fn sum_six_signal_strengths(program: &Vec<&str>) {
-  let mut cycle: u32 = 1;
-  let mut register: i64 = 1;
-  let mut important_cycle_strengths: Vec<i64> = Vec::new();
-
-  for command in program {
-    let args: Vec<&str> = command.split(" ").collect();
-    match args[0] {
-      "addx" => {
-        let out = check_strength(vec![cycle, cycle + 1], register);
-        if out.is_some() {
-          important_cycle_strengths.append(&mut out.unwrap());
-        }
-        cycle += 2;
-        register += args[1].parse::<i64>().unwrap();
-      }
-      "noop" => {
-        let out = check_strength(vec![cycle], register);
-        if out.is_some() {
-          important_cycle_strengths.append(&mut out.unwrap());
-        }
-        cycle += 1;
-      }
-      _ => panic!("Command not recognized!"),
-    }
-  }
-
-  println!(
-    "Cycle strength sum: {}",
-    important_cycle_strengths.iter().sum::<i64>()
-  )
+pub fn sum_six_signal_strengths(program: &Vec<&str>) -> i64 {
+  let mut strength = SignalStrength::default();
+  execute(program, &mut [&mut strength]);
+  strength.sum
 }
 
 /// Day 10 Part 2 -- https://adventofcode.com/2022/day/10#part2
@@ -54,60 +35,156 @@ pub fn sum_six_signal_strengths(program: &Vec<&str>) {
 /// a CRT display. The description is a bit complicated, read it via the link
 /// if you're interested, but the TL;DR: is print out the CRT display
 /// and the solution is the 8 capital letters contained within it.
+pub fn read_crt_display(program: &Vec<&str>) -> String {
+  let pixels = draw_crt_display(program)
+    .iter()
+    .map(|row| row.iter().map(|p| *p == "#").collect())
+    .collect::<Vec<Vec<bool>>>();
+
+  if let Some(letters) = ocr::recognize(&pixels) {
+    return letters;
+  }
+
+  print_crt_display(program);
+  eprintln!("Run `trace 2022 10` to see X and the CRT on every cycle");
+  panic!("Unable to read letters from CRT display");
+}
+
+/// Prints the CRT display row by row for reading by eye
 pub fn print_crt_display(program: &Vec<&str>) {
-  let mut cycle: i32 = 1;
-  let mut register: i32 = 1;
-  let mut current_row: Vec<&str> = vec![];
-
-  for command in program {
-    let args: Vec<&str> = command.split(" ").collect();
-
-    match args[0] {
-      "addx" => {
-        current_row.push(get_pixel_state(cycle, register));
-        current_row.push(get_pixel_state(cycle + 1, register));
-        cycle += 2;
-        register += args[1].parse::<i32>().unwrap();
-      }
-      "noop" => {
-        current_row.push(get_pixel_state(cycle, register));
-        cycle += 1;
-      }
-      _ => panic!("Command not recognized!"),
+  for row in draw_crt_display(program) {
+    println!("{:?}", row.join(""));
+  }
+}
+
+/// Trace export for day 10: a table of every cycle with the instruction
+/// in flight, X during the cycle, the CRT position and whether that pixel
+/// gets lit. `cycles` limits it to an inclusive range.
+pub fn cycle_trace(program: &Vec<&str>, cycles: Option<RangeInclusive<u64>>) -> String {
+  let mut trace = Trace {
+    cycles,
+    rows: Vec::new(),
+  };
+  execute(program, &mut [&mut trace]);
+
+  let width = trace
+    .rows
+    .iter()
+    .map(|row| row.1.len())
+    .chain(["instruction".len()])
+    .max()
+    .unwrap();
+  let mut table = format!("cycle  {:width$}  {:>4}  {:>3}  pixel", "instruction", "X", "CRT");
+  for (cycle, instruction, x, position, lit) in trace.rows {
+    let pixel = if lit { '#' } else { '.' };
+    table += &format!("\n{:>5}  {:width$}  {:>4}  {:>3}  {}", cycle, instruction, x, position, pixel);
+  }
+  table
+}
+
+/// Helper function that runs the program and collects each 40 pixel CRT row
+fn draw_crt_display(program: &[&str]) -> Vec<Vec<&'static str>> {
+  let mut crt = Crt::default();
+  execute(program, &mut [&mut crt]);
+  crt.rows
+}
+
+/// Helper function that runs the program on the device CPU, with `X`
+/// starting at 1, showing every cycle to `observers`
+fn execute(program: &[&str], observers: &mut [&mut dyn Observer]) {
+  let ops = cpu::parse_program(program).unwrap_or_else(|e| panic!("Invalid program, {}", e));
+  let mut device = Cpu::new(ops);
+  device.registers['x'] = 1;
+  device.run(observers);
+}
+
+/// Sums the signal strength (cycle * X) during cycles 20, 60, 100, ...
+#[derive(Debug, Default)]
+struct SignalStrength {
+  sum: i64,
+}
+
+impl Observer for SignalStrength {
+  fn tick(&mut self, tick: &Tick) {
+    if tick.cycle % 40 == 20 {
+      self.sum += tick.cycle as i64 * tick.registers['x'];
     }
+  }
+}
 
-    if current_row.len() >= 40 {
-      println!("{:?}  Cycle: {cycle}", current_row[0..40].join(""));
-      current_row.drain(0..40);
+/// Draws one pixel per cycle, lit when the 3 pixel wide sprite centred
+/// on X covers the pixel being drawn
+#[derive(Debug, Default)]
+struct Crt {
+  rows: Vec<Vec<&'static str>>,
+}
+
+impl Observer for Crt {
+  fn tick(&mut self, tick: &Tick) {
+    let position = crt_position(tick.cycle);
+    if position == 0 {
+      self.rows.push(Vec::with_capacity(CRT_WIDTH as usize));
     }
+    let pixel = match sprite_covers(tick.registers['x'], position) {
+      true => "#",
+      false => ".",
+    };
+    self.rows.last_mut().unwrap().push(pixel);
   }
 }
 
-/// Helper function to check if a CRT signal is lit based on register pos + cycle
-fn get_pixel_state(cycle: i32, register: i32) -> &'static str {
-  let crt_pos = (cycle - 1) % 40;
-  if vec![crt_pos - 1, crt_pos, crt_pos + 1].contains(&register) {
-    return "#";
-  } else {
-    return ".";
+/// Records a row per cycle for [`cycle_trace`]
+#[derive(Debug)]
+struct Trace {
+  cycles: Option<RangeInclusive<u64>>,
+  rows: Vec<(u64, String, i64, i64, bool)>,
+}
+
+impl Observer for Trace {
+  fn tick(&mut self, tick: &Tick) {
+    if self.cycles.as_ref().is_some_and(|range| !range.contains(&tick.cycle)) {
+      return;
+    }
+    let x = tick.registers['x'];
+    let position = crt_position(tick.cycle);
+    self
+      .rows
+      .push((tick.cycle, tick.op.source.clone(), x, position, sprite_covers(x, position)));
   }
 }
 
-/// Helper function to check the strength of a set of cycles (Part 1)
-fn check_strength(cycles: Vec<u32>, register_value: i64) -> Option<Vec<i64>> {
-  let target_strengths: Vec<u32> = vec![20, 60, 100, 140, 180, 220];
-  let cycles_that_matter = cycles
-    .iter()
-    .filter(|n| target_strengths.contains(n))
-    .collect::<Vec<&u32>>();
-  if cycles_that_matter.len() == 0 {
-    return None;
-  } else {
-    return Some(
-      cycles_that_matter
-        .iter()
-        .map(|cycle| i64::from(**cycle) * (register_value))
-        .collect(),
-    );
+/// Helper function for the column the CRT draws during a cycle
+fn crt_position(cycle: u64) -> i64 {
+  (cycle as i64 - 1) % CRT_WIDTH
+}
+
+/// Helper function to check if a CRT pixel is lit based on the register
+fn sprite_covers(register: i64, position: i64) -> bool {
+  (register - position).abs() <= 1
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::utils::snapshot::{assert_snapshot, render_grid};
+
+  #[test]
+  fn crt_display_snapshot() {
+    // With the register stuck at 1, each row only lights the first three pixels
+    let program = vec!["noop"; 240];
+    let display = draw_crt_display(&program);
+    assert_snapshot!("day_10_crt", render_grid(&display, |p| p.chars().next().unwrap()));
+  }
+
+  #[test]
+  fn traces_a_cycle_range() {
+    let program = vec!["noop", "addx 3", "addx -5"];
+    let expected = "\
+cycle  instruction     X  CRT  pixel
+    2  addx 3          1    1  #
+    3  addx 3          1    2  #
+    4  addx -5         4    3  #";
+    assert_eq!(cycle_trace(&program, Some(2..=4)), expected);
+    assert_eq!(cycle_trace(&program, None).lines().count(), 6);
   }
 }
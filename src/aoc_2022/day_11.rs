@@ -1,4 +1,9 @@
 #![allow(dead_code)]
+use crate::utils::{
+  math,
+  parse::{self, Block},
+};
+
 #[derive(Debug, Default, Clone, PartialEq)]
 enum Operation {
   #[default]
@@ -42,56 +47,55 @@ impl Monkey {
 /// Count the total number of times the monkeys evaluate the statements
 /// the course of 20 cycles. The total "monkey business" is the product (*)
 /// of the two most active monkeys over the duration.
-pub fn calculate_monkey_business(monkey_notes: &Vec<&str>) {
+pub fn calculate_monkey_business(monkey_notes: &Vec<&str>) -> usize {
   let mut monkeys = parse_monkeys(monkey_notes);
   for (i, monkey) in monkeys.iter().enumerate() {
     println!("Monkey {i}: {:?}", monkey)
   }
 
-  for _ in 0..20 {
+  run_rounds(&mut monkeys, 20, true);
+
+  let specific_monkey_business = calculate_specific_monkey_business(&monkeys);
+  println!("Monkey business for top 2 apes: {specific_monkey_business}");
+  specific_monkey_business
+}
+
+/// Day 11 Part 2 -- https://adventofcode.com/2022/day/11#part2
+///
+/// Same as part 1 except over the course of 10,000 cycles instead of 20,
+/// and your worry no longer drops by a third after each inspection. The
+/// worry levels get far too big to track, but every monkey only cares
+/// whether an item divides by its test amount, so they can be kept
+/// modulo the lcm of all the test amounts without changing any throws.
+pub fn calculate_big_monkey_business(monkey_notes: &Vec<&str>) -> usize {
+  let mut monkeys = parse_monkeys(monkey_notes);
+
+  run_rounds(&mut monkeys, 10_000, false);
+
+  calculate_specific_monkey_business(&monkeys)
+}
+
+fn run_rounds(monkeys: &mut [Monkey], rounds: usize, anxiety_calms: bool) {
+  let worry_modulus = math::lcm_of(monkeys.iter().map(|m| m.test_amount));
+
+  for _ in 0..rounds {
     for i in 0..monkeys.len() {
       let mut item_set = monkeys[i].items.clone();
       monkeys[i].clear_items();
 
-      while item_set.len() > 0 {
-        let current = item_set.pop().unwrap();
+      while let Some(current) = item_set.pop() {
         monkeys[i].inspection_count += 1;
-        let (next_location, next_value) = get_next_item_location(current, &monkeys[i], false);
+        let (next_location, mut next_value) = get_next_item_location(current, &monkeys[i], anxiety_calms);
+        // Dividing by 3 doesn't play nicely with the modulus, but part 1 stays small anyway
+        if !anxiety_calms {
+          next_value %= worry_modulus;
+        }
         monkeys[next_location].add_item(next_value);
       }
     }
   }
-
-  let specific_monkey_business = calculate_specific_monkey_business(&monkeys);
-  println!("Monkey business for top 2 apes: {specific_monkey_business}");
 }
 
-/// Part 2 is the same as part 1 except over the course of 10,000 cycles instead of 20
-// pub fn calculate_big_monkey_business(monkey_notes: &Vec<&str>) {
-//   let mut monkeys = parse_monkeys(monkey_notes);
-//   let start_timestamp = SystemTime::now();
-
-//   for _ in 0..10 {
-//     for i in 0..monkeys.len() {
-//       let mut item_set =  monkeys[i].items.clone();
-//       monkeys[i].clear_items();
-
-//       while item_set.len() > 0 {
-//         let current = item_set.pop().unwrap();
-//         monkeys[i].inspection_count += 1;
-//         let (next_location, next_value) = get_next_item_location(current, &monkeys[i], false);
-//         monkeys[next_location].add_item(next_value);
-//       }
-
-//       debug_monkeys(&monkeys, i);
-//     }
-//   }
-//   println!("Elapsed time: {:?}", start_timestamp.elapsed());
-
-//   let specific_monkey_business = calculate_specific_monkey_business(&monkeys);
-//   println!("Monkey business for top 2 apes: {specific_monkey_business}");
-// }
-
 fn debug_monkeys(monkeys: &Vec<Monkey>, round: usize) {
   println!("\n Round {round}: ");
   for (i, monkey) in monkeys.clone().iter().enumerate() {
@@ -142,16 +146,14 @@ fn get_next_item_location(item: usize, monkey: &Monkey, anxiety_calms: bool) ->
 /// Parses monkey data from input vec of strings into a vec of monkeys
 #[allow(dead_code)]
 fn parse_monkeys(monkey_notes: &Vec<&str>) -> Vec<Monkey> {
-  let mut monkeys: Vec<Monkey> = Vec::new();
-  let mut monkey_template = Monkey::default();
+  parse::blocks(monkey_notes).iter().map(parse_monkey).collect()
+}
 
-  for line in monkey_notes {
-    if line.len() == 0 {
-      monkeys.push(monkey_template.clone());
-      monkey_template = Monkey::default();
-      continue;
-    }
+/// Parses a single monkey's block of notes
+fn parse_monkey(block: &Block) -> Monkey {
+  let mut monkey_template = Monkey::default();
 
+  for line in block.lines {
     let split: Vec<&str> = line.trim().split(":").collect();
     let info_type = split[0];
 
@@ -195,5 +197,10 @@ fn parse_monkeys(monkey_notes: &Vec<&str>) -> Vec<Monkey> {
     }
   }
 
-  return monkeys;
+  // Every worry level is checked with `% test_amount`, so zero can't work
+  if monkey_template.test_amount == 0 {
+    panic!("Monkey on line {} needs a non-zero test amount", block.line);
+  }
+
+  monkey_template
 }
@@ -1,71 +1,53 @@
-#[derive(Debug)]
-struct Position(usize, usize);
+use crate::utils::{geometry::Vec2, grid::Grid, search};
+
+/// Day 12 Part 1 -- https://adventofcode.com/2022/day/12
+///
+/// Your input is a heightmap of the area with elevations running from
+/// a (lowest) to z (highest), your current position S (elevation a) and
+/// the best signal spot E (elevation z). Each step can move you up at
+/// most one elevation, but you can drop down as far as you like.
+///
+/// What's the fewest steps required to move from S to E?
+pub fn find_shortest_path(heightmap: &Vec<&str>) -> usize {
+  let map = Grid::parse(heightmap);
+  let start = Vec2::from(map.find(&'S').expect("No start position found in heightmap."));
+
+  shortest_climb(&map, [start])
+}
 
-#[allow(dead_code)]
-pub fn find_shortest_path(heightmap: &Vec<&str>) {
-  let ref_str = String::from("SEabcdefghijklmnopqrstuvwxyz");
-  let typed_heightmap: Vec<Vec<usize>> = heightmap
+/// Day 12 Part 2 -- https://adventofcode.com/2022/day/12#part2
+///
+/// To turn the spot into a hiking trail, it should start as low as
+/// possible. What's the fewest steps required to move from any square
+/// with elevation a to E?
+pub fn find_shortest_hike(heightmap: &Vec<&str>) -> usize {
+  let map = Grid::parse(heightmap);
+  let starts = map
     .iter()
-    .map(|row| {
-      return row
-        .chars()
-        .map(|ch| ref_str.find(ch).unwrap())
-        .collect::<Vec<usize>>();
-    })
-    .collect();
-
-  let mut start_pos: Position = Position(usize::MAX, usize::MAX);
-  let mut target_pos: Position = Position(usize::MAX, usize::MAX);
-  let height = typed_heightmap.len();
-  let width = typed_heightmap[0].len();
+    .filter(|(_, ch)| elevation(**ch) == 0)
+    .map(|(pos, _)| Vec2::from(pos));
 
-  for row in 0..height {
-    for col in 0..width {
-      if typed_heightmap[row][col] == 0 {
-        start_pos = Position(row, col)
-      }
-      if typed_heightmap[row][col] == 1 {
-        target_pos = Position(row, col)
-      }
-    }
-  }
-
-  if vec![start_pos.0, start_pos.1, target_pos.0, target_pos.1].contains(&usize::MAX) {
-    panic!(
-      "Positions improperly initialized: start {:?}  end {:?}",
-      start_pos, target_pos
-    );
-  } else {
-    println!(
-      "Initialized Positions:\n  Start: {:?}\n  End: {:?}",
-      start_pos, target_pos
-    )
-  }
-
-  // BFS Implementation
-  let mut to_explore: Vec<Position> = vec![start_pos];
-  let _visited: Vec<Position> = vec![];
-
-  while !to_explore.is_empty() {
-    let current = to_explore.pop().unwrap();
+  shortest_climb(&map, starts)
+}
 
-    if current.0 == target_pos.0 && current.1 == target_pos.1 {
-      break;
-    }
+// Helper functions
+fn shortest_climb(map: &Grid<char>, starts: impl IntoIterator<Item = Vec2>) -> usize {
+  let climbable = |pos: &Vec2| {
+    let height = elevation(map[*pos]);
+    pos
+      .neighbours4()
+      .filter(move |next| map.get(*next).is_some_and(|ch| elevation(*ch) <= height + 1))
+  };
+
+  search::bfs(starts, climbable, |pos| map[*pos] == 'E')
+    .expect("No path found to the best signal.")
+    .cost
+}
 
-    let row = current.0;
-    let col = current.1;
-    if row > 0 {
-      to_explore.insert(0, Position(row - 1, col))
-    } // left
-    if row < height {
-      to_explore.insert(0, Position(row + 1, col))
-    } // right
-    if col > 0 {
-      to_explore.insert(0, Position(row, col - 1))
-    } // up
-    if col < width {
-      to_explore.insert(0, Position(row, col + 1))
-    } // down
+fn elevation(ch: char) -> u32 {
+  match ch {
+    'S' => 0,
+    'E' => 25,
+    _ => ch as u32 - 'a' as u32,
   }
 }
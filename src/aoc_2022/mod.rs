@@ -10,63 +10,130 @@ mod day_10;
 mod day_11;
 mod day_12;
 
-pub fn run_day_number(day: u32, input: &Vec<&str>) {
-  match day {
-    1 => {
-      let most_calories = day_01::find_elf_carrying_most_calories(input);
-      let top_caloriie_sum = day_01::find_top_three_calorie_sum(input);
-      println!("\nDay 1:");
-      println!("  Max calorie elf:   {:?}", most_calories);
-      println!("  Top 3 calorie sum: {:?}", top_caloriie_sum);
-    }
+use crate::runner::{Part, Tracer};
 
-    2 => {
-      let total_rps_score = day_02::calculate_total_score(input);
-      let adjusted_rps_score = day_02::calculate_total_score_adjusted(input);
-      println!("\nDay 2:");
-      println!("  Total RPS score:    {:?}", total_rps_score);
-      println!("  Adjusted RPS score: {:?}", adjusted_rps_score);
-    }
+pub fn day_parts(day: u32) -> Option<Vec<Part>> {
+  let parts = match day {
+    1 => vec![
+      Part {
+        label: "Max calorie elf",
+        solve: |input| day_01::find_elf_carrying_most_calories(input).to_string(),
+      },
+      Part {
+        label: "Top 3 calorie sum",
+        solve: |input| day_01::find_top_three_calorie_sum(input).to_string(),
+      },
+    ],
 
-    3 => {
-      let dup_priority_sum = day_03::find_priority_sum_of_dups(input);
-      let badge_priority_sum = day_03::find_priority_sum_of_team_badges(input);
-      println!("\nDay 3:");
-      println!("  Duplicate priority sum:  {:?}", dup_priority_sum);
-      println!("  Team badge priority sum: {:?}", badge_priority_sum);
-    }
+    2 => vec![
+      Part {
+        label: "Total RPS score",
+        solve: |input| day_02::calculate_total_score(input).to_string(),
+      },
+      Part {
+        label: "Adjusted RPS score",
+        solve: |input| day_02::calculate_total_score_adjusted(input).to_string(),
+      },
+    ],
+
+    3 => vec![
+      Part {
+        label: "Duplicate priority sum",
+        solve: |input| day_03::find_priority_sum_of_dups(input).to_string(),
+      },
+      Part {
+        label: "Team badge priority sum",
+        solve: |input| day_03::find_priority_sum_of_team_badges(input).to_string(),
+      },
+    ],
 
     // TODO: Day 4
-    5 => {
-      let rearr_msg = day_05::find_rearrangement_message(input);
-      let multimove_msg = day_05::find_multimove_message(input);
-      println!("\nDay 5:");
-      println!("  Rearrangement message: {:?}", rearr_msg);
-      println!("  Multi-move message:    {:?}", multimove_msg);
-    }
+    5 => vec![
+      Part {
+        label: "Rearrangement message",
+        solve: |input| day_05::find_rearrangement_message(input),
+      },
+      Part {
+        label: "Multi-move message",
+        solve: |input| day_05::find_multimove_message(input),
+      },
+    ],
+
+    6 => vec![
+      Part {
+        label: "Characters to packet",
+        solve: |input| day_06::characters_before_start_of_packet(input).to_string(),
+      },
+      Part {
+        label: "Characters to big packet",
+        solve: |input| day_06::characters_before_start_of_packet_big(input).to_string(),
+      },
+    ],
+
+    7 => vec![
+      Part {
+        label: "Directory sum under 100k",
+        solve: |input| {
+          day_07::find_sum_dirs_under_100000(input);
+          String::from("-")
+        },
+      },
+    ],
+
+    8 => vec![
+      Part {
+        label: "Total visible trees",
+        solve: |input| day_08::get_visible_tree_count(input).to_string(),
+      },
+      Part {
+        label: "Most scenic tree score",
+        solve: |input| day_08::get_visible_tree_count(input).to_string(),
+      },
+    ],
 
-    6 => {
-      let chars_to_packet = day_06::characters_before_start_of_packet(input);
-      let chars_to_packet_big = day_06::characters_before_start_of_packet_big(input);
-      println!("\nDay 6:");
-      println!("  Characters to packet:     {:?}", chars_to_packet);
-      println!("  Characters to big packet: {:?}", chars_to_packet_big);
-    }
+    10 => vec![
+      Part {
+        label: "Signal strength sum",
+        solve: |input| day_10::sum_six_signal_strengths(input).to_string(),
+      },
+      Part {
+        label: "CRT letters",
+        solve: |input| day_10::read_crt_display(input),
+      },
+    ],
 
-    7 => {
-      let find_dir_sum = day_07::find_sum_dirs_under_100000(input);
-      println!("\nDay 7:");
-      println!("  Directory Sum Under 10k: {:?}", find_dir_sum);
-    }
+    11 => vec![
+      Part {
+        label: "Monkey business",
+        solve: |input| day_11::calculate_monkey_business(input).to_string(),
+      },
+      Part {
+        label: "Big monkey business",
+        solve: |input| day_11::calculate_big_monkey_business(input).to_string(),
+      },
+    ],
 
-    8 => {
-      let visible_trees = day_08::get_visible_tree_count(input);
-      let most_scenic_tree_score = day_08::get_visible_tree_count(input);
-      println!("\nDay 8:");
-      println!("  Total visible trees: {:?}", visible_trees);
-      println!("  Most scenic tree sum: {:?}", most_scenic_tree_score);
-    }
+    12 => vec![
+      Part {
+        label: "Fewest steps to signal",
+        solve: |input| day_12::find_shortest_path(input).to_string(),
+      },
+      Part {
+        label: "Fewest steps from any a",
+        solve: |input| day_12::find_shortest_hike(input).to_string(),
+      },
+    ],
+
+    _ => return None,
+  };
+
+  Some(parts)
+}
 
-    _ => println!("Code for day {:?} undefined", day),
-  }
+pub fn day_tracer(day: u32) -> Option<Tracer> {
+  let tracer: Tracer = match day {
+    10 => day_10::cycle_trace,
+    _ => return None,
+  };
+  Some(tracer)
 }
@@ -1,6 +1,7 @@
-use regex::Regex;
 use std::collections::HashMap;
 
+use crate::utils::parse;
+
 /// Day 4, Part 1 -- https://adventofcode.com/2023/day/4
 ///
 /// The elf at the top of the gondola says that an elf, the gardener,
@@ -19,14 +20,12 @@ use std::collections::HashMap;
 /// Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
 /// Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
 pub fn calculate_scratcher_points(scratcher: &Vec<&str>) -> u32 {
-  let re = Regex::new("([0-9]{1,})").unwrap();
-
   return scratcher
     .iter()
     .map(|line| {
       let scratcher_parts: Vec<&str> = line.split([':', '|']).collect();
-      let winners: Vec<u32> = parse_nums(scratcher_parts[1], &re);
-      let picks: Vec<u32> = parse_nums(scratcher_parts[2], &re);
+      let winners: Vec<u32> = parse::unsigned_ints(scratcher_parts[1]);
+      let picks: Vec<u32> = parse::unsigned_ints(scratcher_parts[2]);
       let found_winners = picks.iter().filter(|n| winners.contains(n)).count();
       if found_winners > 0 {
         return 2_u32.pow((found_winners - 1).try_into().unwrap());
@@ -50,7 +49,6 @@ pub fn calculate_scratcher_points(scratcher: &Vec<&str>) -> u32 {
 /// How many cards (including all the original scratch cards) do you
 /// end up with?
 pub fn sum_total_scratchers(scratch_cards: &Vec<&str>) -> usize {
-  let re = Regex::new("([0-9]{1,})").unwrap();
   let mut cards: HashMap<usize, usize> = HashMap::new(); // <card_id,card_count>
 
   for i in 1..=scratch_cards.len() {
@@ -59,8 +57,8 @@ pub fn sum_total_scratchers(scratch_cards: &Vec<&str>) -> usize {
 
   for i in 1..=scratch_cards.len() {
     let scratcher_parts: Vec<&str> = scratch_cards[i - 1].split([':', '|']).collect();
-    let winners = parse_nums(scratcher_parts[1], &re);
-    let picks = parse_nums(scratcher_parts[2], &re);
+    let winners: Vec<u32> = parse::unsigned_ints(scratcher_parts[1]);
+    let picks: Vec<u32> = parse::unsigned_ints(scratcher_parts[2]);
     let found_winners = picks.iter().filter(|n| winners.contains(n)).count();
     if found_winners == 0 {
       continue;
@@ -75,9 +73,3 @@ pub fn sum_total_scratchers(scratch_cards: &Vec<&str>) -> usize {
   return cards.values().map(|n| *n).sum();
 }
 
-// Helpers
-fn parse_nums(s: &str, re: &Regex) -> Vec<u32> {
-  re.find_iter(s)
-    .filter_map(|digits| digits.as_str().parse().ok())
-    .collect()
-}
@@ -1,4 +1,7 @@
-use regex::Regex;
+use crate::utils::{
+  interval::{Interval, IntervalSet},
+  parse,
+};
 
 /// Day 5, Part 1 -- https://adventofcode.com/2023/day/5
 ///
@@ -26,13 +29,9 @@ use regex::Regex;
 ///
 /// What is the lowest location number that corresponds to any of the initial seeds?
 pub fn find_lowest_initial_seed_location(almanac: &Vec<&str>) -> i64 {
-  let re = Regex::new("([0-9]{1,})").unwrap();
-  let seed_inputs = re
-    .find_iter(almanac[0])
-    .map(|n| n.as_str().parse::<i64>().unwrap())
-    .collect::<Vec<i64>>();
+  let seed_inputs: Vec<i64> = parse::unsigned_ints(almanac[0]);
 
-  let almanac_pages = parse_entries(almanac, &re);
+  let almanac_pages = parse_entries(almanac);
   return seed_inputs
     .iter()
     .map(|seed| {
@@ -46,6 +45,39 @@ pub fn find_lowest_initial_seed_location(almanac: &Vec<&str>) -> i64 {
     .unwrap();
 }
 
+/// Day 5, Part 2 -- https://adventofcode.com/2023/day/5#part2
+///
+/// Looking closer at the almanac, the seeds line is actually pairs of
+/// numbers. The first of each pair is the start of a range of seeds and
+/// the second is how many seeds are in it, so the almanac covers far too
+/// many seeds to map one by one.
+///
+/// What is the lowest location number that corresponds to any of the
+/// initial seed ranges?
+pub fn find_lowest_seed_range_location(almanac: &Vec<&str>) -> i64 {
+  let mut seeds: IntervalSet<i64> = parse::unsigned_ints(almanac[0])
+    .chunks(2)
+    .map(|pair| Interval::from_len(pair[0], pair[1]))
+    .collect();
+
+  for page in parse_entries(almanac) {
+    seeds = update_ranges_by_almanac_page(&seeds, &page);
+  }
+
+  seeds.min().expect("No seeds left after processing the almanac.")
+}
+
+fn update_ranges_by_almanac_page(seeds: &IntervalSet<i64>, map: &[SeedRange]) -> IntervalSet<i64> {
+  let mut unmapped = seeds.clone();
+  let mut mapped: IntervalSet<i64> = IntervalSet::new();
+  for range in map {
+    let source = IntervalSet::from(range.source());
+    mapped = mapped.union(&unmapped.intersection(&source).shift(range.output - range.input));
+    unmapped = unmapped.difference(&source);
+  }
+  mapped.union(&unmapped)
+}
+
 fn update_by_almanac_page(seed: i64, map: &Vec<SeedRange>) -> i64 {
   for range in map {
     let updated_value = range.process_seed(seed);
@@ -64,15 +96,19 @@ struct SeedRange {
 }
 
 impl SeedRange {
+  pub fn source(&self) -> Interval<i64> {
+    Interval::from_len(self.input, self.range)
+  }
+
   pub fn process_seed(&self, seed: i64) -> i64 {
-    if seed >= self.input && seed <= self.input + self.range {
-      return self.output + (seed - self.input).abs(); // output = output + diff
+    if self.source().contains(seed) {
+      return self.output + (seed - self.input); // output = output + diff
     }
     return seed;
   }
 }
 
-fn parse_entries(almanac: &Vec<&str>, re: &Regex) -> Vec<Vec<SeedRange>> {
+fn parse_entries(almanac: &Vec<&str>) -> Vec<Vec<SeedRange>> {
   let mut entries: Vec<Vec<SeedRange>> = Vec::new();
   let mut temp: Vec<SeedRange> = Vec::new();
 
@@ -87,7 +123,7 @@ fn parse_entries(almanac: &Vec<&str>, re: &Regex) -> Vec<Vec<SeedRange>> {
         temp = Vec::new();
       }
       3 => {
-        let parts = parse_nums(almanac[i], &re);
+        let parts = parse::unsigned_ints(almanac[i]);
         temp.push(SeedRange {
           input: parts[1],
           output: parts[0],
@@ -102,9 +138,3 @@ fn parse_entries(almanac: &Vec<&str>, re: &Regex) -> Vec<Vec<SeedRange>> {
   return entries;
 }
 
-// helpers
-fn parse_nums(s: &str, re: &Regex) -> Vec<i64> {
-  re.find_iter(s)
-    .filter_map(|digits| digits.as_str().parse().ok())
-    .collect()
-}
@@ -1,5 +1,6 @@
 use itertools::Itertools;
-use regex::Regex;
+
+use crate::utils::parse;
 
 /// Day 6, Part 1 -- https://adventofcode.com/2023/day/6
 ///
@@ -26,15 +27,9 @@ use regex::Regex;
 /// your total options to win, and the puzzle output, would be 4 * 8 * 9.
 /// How many ways are there to win in your input?
 pub fn find_multisolution_product(record_sheet: &Vec<&str>) -> u64 {
-  let re = Regex::new("([0-9]{1,})").unwrap();
   let times_and_distances = record_sheet
     .iter()
-    .map(|s| {
-      return re
-        .find_iter(s)
-        .map(|n| n.as_str().parse::<u64>().unwrap())
-        .collect::<Vec<u64>>();
-    })
+    .map(|s| parse::unsigned_ints(s))
     .collect::<Vec<Vec<u64>>>();
 
   let mut valid_race_opts: Vec<u64> = Vec::new();
@@ -59,17 +54,9 @@ pub fn find_multisolution_product(record_sheet: &Vec<&str>) -> u64 {
 /// have 71530s to cover 940200mm. Given this new info, how many ways
 /// are there for you to win this new race?
 pub fn find_solution_large_input(record_sheet: &Vec<&str>) -> u64 {
-  let re = Regex::new("([0-9]{1,})").unwrap();
   let time_distance = record_sheet
     .iter()
-    .map(|s| {
-      re.find_iter(s)
-        .map(|n_str| n_str.as_str().to_owned())
-        .collect::<Vec<String>>()
-        .join("")
-        .parse::<u64>()
-        .unwrap()
-    })
+    .map(|s| parse::unsigned_ints::<String>(s).join("").parse::<u64>().unwrap())
     .collect_vec();
   let min = find_min_valid_time(time_distance[0], time_distance[1]);
   let max = find_max_valid_time(time_distance[0], time_distance[1]);
@@ -1,7 +1,6 @@
 use std::{cmp::Ordering, collections::HashMap};
 
 use itertools::Itertools;
-use regex::Regex;
 
 /// Day 7, Part 1 -- https://adventofcode.com/2023/day/7
 ///
@@ -106,8 +105,7 @@ impl Hand {
 
   pub fn from_str_wild(s: &str) -> Hand {
     let parts = s.split(" ").collect_vec();
-    let re = Regex::new(r"(J)").unwrap();
-    let cards_jacks_stripped = re.replace_all(parts[0], "");
+    let cards_jacks_stripped = parts[0].replace('J', "");
     let mut default_hand = Hand {
       cards: parts[0]
         .split("")
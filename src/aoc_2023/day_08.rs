@@ -1,7 +1,11 @@
 use std::collections::HashMap;
 
 use itertools::Itertools;
-use regex::Regex;
+
+use crate::utils::{
+  dot::{Graph, Shape},
+  math, parse,
+};
 
 /// Day 8, Part 1 -- https://adventofcode.com/2023/day/8
 ///
@@ -64,33 +68,17 @@ pub fn find_steps_to_zzz(directions: &Vec<&str>) -> usize {
 pub fn ghost_traverse_to_exit_steps(directions: &Vec<&str>) -> usize {
   let lr_directions = directions[0].split("").filter(|s| s != &"").collect_vec();
   let map = parse_map(directions);
-  let positions = map
-    .keys()
-    .filter(|s| s.chars().last().unwrap() == 'A')
-    .collect_vec();
-
-  let steps = 0;
-
-  let out = positions
-    .iter()
-    .map(|pos| {
-      let mut all_positions: Vec<String> = Vec::new();
-      let mut current = *pos;
-
-      let mut step = 0;
-      while !all_positions.contains(&format!(
-        "{}_{}",
-        current,
-        lr_directions[step % lr_directions.len()]
-      )) {
-        // cache that it happened
-        all_positions.push(format!(
-          "{}_{}",
-          current,
-          lr_directions[step % lr_directions.len()]
-        ));
 
-        // step
+  // Every ghost's route loops back round to its exit exactly as many steps
+  // after reaching it as it took to get there in the first place, so all
+  // the ghosts line up on the lcm of their individual step counts.
+  let exit_steps = map
+    .keys()
+    .filter(|s| s.ends_with('A'))
+    .map(|start| {
+      let mut current = start;
+      let mut steps = 0;
+      while !current.ends_with('Z') {
         let next_directions = map.get(current).unwrap();
         match lr_directions[steps % lr_directions.len()] {
           "L" => current = &next_directions.0,
@@ -100,31 +88,38 @@ pub fn ghost_traverse_to_exit_steps(directions: &Vec<&str>) -> usize {
             lr_directions[steps % lr_directions.len()]
           ),
         }
-        step += 1;
+        steps += 1;
       }
-
-      println!("Found repeat for start {} at step {}", pos, step);
-      return all_positions;
+      steps
     })
     .collect_vec();
 
-  for v in out {
-    println!("{:?}", v);
-  }
+  math::lcm_of(exit_steps)
+}
 
-  return 0;
+/// Graph export for day 8: every node with its left and right links.
+/// Starting nodes (ending in A) are houses and exits (ending in Z) are
+/// double circles, which makes each ghost's separate loop easy to spot.
+pub fn node_graph(directions: &Vec<&str>) -> Graph {
+  let mut graph = Graph::digraph("day_08");
+  for (node, (left, right)) in parse_map(directions).iter().sorted() {
+    if node.ends_with('A') {
+      graph.node(node).shape(Shape::House);
+    } else if node.ends_with('Z') {
+      graph.node(node).shape(Shape::DoubleCircle);
+    }
+    graph.edge(node, left).label("L");
+    graph.edge(node, right).label("R");
+  }
+  graph
 }
 
 // Helpers
 fn parse_map(directions: &Vec<&str>) -> HashMap<String, (String, String)> {
-  let re = Regex::new(r"([A-Z]{3})").unwrap();
   let mut map = HashMap::new();
-  for i in 2..directions.len() {
-    let matches = re
-      .find_iter(directions[i])
-      .map(|m| m.as_str().to_owned())
-      .collect_vec();
-    map.insert(matches[0].clone(), (matches[1].clone(), matches[2].clone()));
+  for line in directions.iter().skip(2).filter(|line| !line.is_empty()) {
+    let node = parse::scan("{} = ({}, {})", line).unwrap_or_else(|| panic!("Invalid map line: '{}'", line));
+    map.insert(node[0].to_owned(), (node[1].to_owned(), node[2].to_owned()));
   }
   return map;
 }
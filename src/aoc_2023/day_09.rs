@@ -1,4 +1,4 @@
-use itertools::Itertools;
+use crate::utils::{parse, polynomial};
 
 /// Day 9, Part 1 -- https://adventofcode.com/2023/day/9
 ///
@@ -12,26 +12,11 @@ use itertools::Itertools;
 /// the next updraft is going to be.
 ///
 /// What's the sum of the extrapolated next values?
-pub fn extrapolate_pattern_sum(patterns: &Vec<&str>) -> i32 {
-  let mut extrapolated_pattern_sum = 0;
-  for pattern in patterns {
-    let src_pattern = pattern
-      .split(" ")
-      .map(|s| s.parse::<i32>().unwrap())
-      .collect_vec();
-
-    let mut extrapolated_pattern = extrapolate_diff(&src_pattern);
-    let mut patterns = vec![extrapolated_pattern.clone()];
-    while !extrapolated_pattern.iter().all(|n| n == &0) {
-      extrapolated_pattern = extrapolate_diff(&extrapolated_pattern);
-      patterns.push(extrapolated_pattern.clone());
-    }
-
-    let pattern_addend: i32 = patterns.iter().map(|arr| arr.last().unwrap()).sum();
-    extrapolated_pattern_sum += src_pattern.last().unwrap() + pattern_addend
-  }
-
-  return extrapolated_pattern_sum;
+pub fn extrapolate_pattern_sum(patterns: &Vec<&str>) -> i128 {
+  parse_readings(patterns)
+    .iter()
+    .map(|reading| polynomial::next_value(reading))
+    .sum()
 }
 
 /// Day 9, Part 2
@@ -41,37 +26,18 @@ pub fn extrapolate_pattern_sum(patterns: &Vec<&str>) -> i32 {
 /// should just extrapolate what the previous values would've been.
 ///
 /// What's the sum of the previous extrapolated values?
-pub fn extrapolate_pattern_sum_backward(patterns: &Vec<&str>) -> i32 {
-  let mut extrapolated_pattern_sum: i32 = 0;
-  for pattern in patterns {
-    let src_pattern = pattern
-      .split(" ")
-      .map(|s| s.parse::<i32>().unwrap())
-      .collect_vec();
-
-    let mut extrapolated_pattern = extrapolate_diff(&src_pattern);
-    let mut patterns = vec![extrapolated_pattern.clone()];
-    while !extrapolated_pattern.iter().all(|n| n == &0) {
-      extrapolated_pattern = extrapolate_diff(&extrapolated_pattern);
-      patterns.push(extrapolated_pattern.clone());
-    }
-
-    let mut leading_values: Vec<i32> = vec![0];
-    for row in (0..patterns.len()).rev() {
-      let minus = patterns[row][0] - leading_values.last().unwrap();
-      leading_values.push(minus);
-    }
-
-    extrapolated_pattern_sum += src_pattern.first().unwrap() - leading_values.last().unwrap();
-  }
-
-  return extrapolated_pattern_sum;
+pub fn extrapolate_pattern_sum_backward(patterns: &Vec<&str>) -> i128 {
+  parse_readings(patterns)
+    .iter()
+    .map(|reading| polynomial::previous_value(reading))
+    .sum()
 }
 
-fn extrapolate_diff(src: &Vec<i32>) -> Vec<i32> {
-  let mut diff: Vec<i32> = Vec::new();
-  for i in 0..src.len() - 1 {
-    diff.push(src[i + 1] - src[i]);
-  }
-  return diff;
+// Helpers
+fn parse_readings(patterns: &[&str]) -> Vec<Vec<i128>> {
+  patterns
+    .iter()
+    .filter(|line| !line.is_empty())
+    .map(|line| parse::signed_ints(line))
+    .collect()
 }
@@ -1,9 +1,11 @@
 use itertools::Itertools;
 
-const UP: (isize, isize) = (-1, 0);
-const DOWN: (isize, isize) = (1, 0);
-const LEFT: (isize, isize) = (0, -1);
-const RIGHT: (isize, isize) = (0, 1);
+use crate::utils::{
+  geometry::{Direction, Vec2},
+  grid::Grid,
+  image::{self, Image, Rgb},
+  polygon::Polygon,
+};
 
 /// Day 10, Part 1
 ///
@@ -21,29 +23,76 @@ const RIGHT: (isize, isize) = (0, 1);
 /// starting position to the point furthest away from the starting
 /// position along the main pipe?
 pub fn find_furthest_loop_section(pipe_map: &Vec<&str>) -> isize {
-  let mut padded_map = pipe_map.iter().map(|s| format!(".{}.", s)).collect_vec();
-  padded_map.insert(0, vec!["."; padded_map[0].len()].join(""));
-  padded_map.push(vec!["."; padded_map[0].len()].join(""));
-  let pipe_arr = padded_map
-    .iter()
-    .map(|s| s.split("").filter(|s| s != &"").collect_vec())
-    .collect_vec();
+  let pipe_arr = Grid::parse(pipe_map);
+
+  println!("{}", pipe_arr);
+  let pipe_pieces = trace_loop(&pipe_arr).len() as isize;
+
+  return pipe_pieces / 2;
+}
+
+/// Day 10, Part 2
+///
+/// Prompt is very large, see markdown file:
+/// src\aoc_2023\prompts\day_10.md
+///
+/// The animal's nest must be somewhere inside the main loop. How many
+/// tiles are enclosed by the loop?
+pub fn count_enclosed_tiles(pipe_map: &Vec<&str>) -> usize {
+  let pipe_arr = Grid::parse(pipe_map);
+
+  let pipe_loop = trace_loop(&pipe_arr);
+  image::debug_dump("2023_day_10_loop", || loop_image(&pipe_arr, &pipe_loop));
+
+  // Tiles are lattice points and the loop is a polygon through them
+  Polygon::new(pipe_loop).interior_points()
+}
+
+// Loop in yellow and enclosed tiles in green. Scanning each row, crossing
+// a loop tile that connects north flips between outside and inside.
+fn loop_image(pipe_arr: &Grid<char>, pipe_loop: &[Vec2]) -> Image {
+  let start = pipe_loop[0];
+  let start_north = [pipe_loop[1], pipe_loop[pipe_loop.len() - 1]].contains(&(start + Direction::North));
+  let mut on_loop = pipe_arr.map(|_| false);
+  pipe_loop.iter().for_each(|pos| on_loop[*pos] = true);
 
-  pipe_arr.iter().for_each(|v| println!("{}", v.join("")));
-  let start_pos = find_start_position(&pipe_arr);
+  let mut colors = pipe_arr.map(|_| Rgb::DARK);
+  for row in 0..pipe_arr.height() as isize {
+    let mut inside = false;
+    for col in 0..pipe_arr.width() as isize {
+      let pos = (row, col);
+      if on_loop[pos] {
+        colors[pos] = Rgb::YELLOW;
+        let connects_north = match pipe_arr[pos] {
+          '|' | 'L' | 'J' => true,
+          'S' => start_north,
+          _ => false,
+        };
+        inside ^= connects_north;
+      } else if inside {
+        colors[pos] = Rgb::GREEN;
+      }
+    }
+  }
+  Image::from_grid(&colors, 4, |rgb| *rgb)
+}
+
+// Walks the main loop from S, returning each tile along it in order
+fn trace_loop(pipe_arr: &Grid<char>) -> Vec<Vec2> {
+  let start_pos = Vec2::from(pipe_arr.find(&'S').expect("No starting location found."));
   let starting_pipe = Pipe {
     position: start_pos,
-    connections: find_start_connections(start_pos, &pipe_arr),
+    connections: find_start_connections(start_pos, pipe_arr),
   };
 
   let mut current = starting_pipe.build_positions()[0];
   let mut prev = starting_pipe;
-  let mut pipe_pieces = 1;
+  let mut pipe_pieces = vec![start_pos];
 
   while current != start_pos {
-    match Pipe::new(current, pipe_arr[current.0 as usize][current.1 as usize]) {
+    match Pipe::new(current, pipe_arr[current]) {
       Some(pipe) => {
-        pipe_pieces += 1;
+        pipe_pieces.push(current);
         let temp = *pipe
           .build_positions()
           .iter()
@@ -54,33 +103,33 @@ pub fn find_furthest_loop_section(pipe_map: &Vec<&str>) -> isize {
       }
       None => panic!(
         "Unable to parse pipe {:?} with value {}",
-        current, pipe_arr[current.0 as usize][current.1 as usize]
+        current, pipe_arr[current]
       ),
     }
   }
 
-  return pipe_pieces / 2;
+  pipe_pieces
 }
 
-type Connection = (isize, isize);
+type Connection = Direction;
 
 #[derive(Clone, Debug)]
 struct Pipe {
-  position: (isize, isize),
+  position: Vec2,
   connections: (Connection, Connection),
 }
 
 impl Pipe {
-  pub fn new(position: (isize, isize), value: &str) -> Option<Pipe> {
+  pub fn new(position: Vec2, value: char) -> Option<Pipe> {
     let connections;
     match value {
-      "|" => connections = (UP, DOWN),
-      "-" => connections = (LEFT, RIGHT),
-      "L" => connections = (UP, RIGHT),
-      "J" => connections = (UP, LEFT),
-      "7" => connections = (LEFT, DOWN),
-      "F" => connections = (RIGHT, DOWN),
-      "." => return None,
+      '|' => connections = (Direction::North, Direction::South),
+      '-' => connections = (Direction::West, Direction::East),
+      'L' => connections = (Direction::North, Direction::East),
+      'J' => connections = (Direction::North, Direction::West),
+      '7' => connections = (Direction::West, Direction::South),
+      'F' => connections = (Direction::East, Direction::South),
+      '.' => return None,
       _ => panic!("Pipe symbol unaccounted for: {}", value),
     }
 
@@ -90,43 +139,18 @@ impl Pipe {
     });
   }
 
-  pub fn build_positions(&self) -> Vec<Connection> {
-    return vec![
-      (
-        self.position.0 + self.connections.0 .0,
-        self.position.1 + self.connections.0 .1,
-      ),
-      (
-        self.position.0 + self.connections.1 .0,
-        self.position.1 + self.connections.1 .1,
-      ),
-    ];
+  pub fn build_positions(&self) -> Vec<Vec2> {
+    return vec![self.position + self.connections.0, self.position + self.connections.1];
   }
 }
 
 // Helper functions
-fn find_start_position(pipe_map: &Vec<Vec<&str>>) -> (isize, isize) {
-  for i in 0..pipe_map.len() {
-    for j in 0..pipe_map[0].len() {
-      if pipe_map[i][j] == "S" {
-        return (i.try_into().unwrap(), j.try_into().unwrap());
-      }
-    }
-  }
-  panic!("No starting location found.");
-}
-
-fn find_start_connections(
-  start_pos: (isize, isize),
-  pipe_map: &Vec<Vec<&str>>,
-) -> (Connection, Connection) {
-  let binding = vec![UP, DOWN, LEFT, RIGHT];
-  let ok_dirs = binding
+fn find_start_connections(start_pos: Vec2, pipe_map: &Grid<char>) -> (Connection, Connection) {
+  let ok_dirs = Direction::ALL
     .iter()
     .filter(|dir| {
-      let target = (start_pos.0 + dir.0, start_pos.1 + dir.1);
-      let value = pipe_map[target.0 as usize][target.1 as usize];
-      match Pipe::new(target, &value.to_string()) {
+      let target = start_pos + **dir;
+      match pipe_map.get(target).and_then(|value| Pipe::new(target, *value)) {
         Some(pipe) => pipe.build_positions().contains(&start_pos),
         None => false,
       }
@@ -1,5 +1,7 @@
 use itertools::Itertools;
 
+use crate::utils::grid::Grid;
+
 /// Day 11, Part 1 -- https://adventofcode.com/2023/day/11
 ///
 /// You follow the signs for "Hot Springs" and eventually come across
@@ -18,7 +20,7 @@ use itertools::Itertools;
 ///
 /// What is the sum of the lengths between every pair of galaxies?  
 pub fn find_distance_sum(unexpanded_star_map: &Vec<&str>) -> u32 {
-  let star_map = expand_star_map(unexpanded_star_map);
+  let star_map = Grid::parse(unexpanded_star_map);
   let (h_gaps, v_gaps) = find_gaps(&star_map);
   let stars: Vec<(i32, i32)> = find_stars(&star_map);
 
@@ -45,7 +47,7 @@ pub fn find_distance_sum(unexpanded_star_map: &Vec<&str>) -> u32 {
 ///
 /// What's the sum of the new lengths between stars at scale?
 pub fn find_scaled_distance_sum(unexpanded_star_map: &Vec<&str>) -> u64 {
-  let star_map = expand_star_map(unexpanded_star_map);
+  let star_map = Grid::parse(unexpanded_star_map);
   let (h_gaps, v_gaps) = find_gaps(&star_map);
   let stars: Vec<(i32, i32)> = find_stars(&star_map);
 
@@ -64,46 +66,27 @@ pub fn find_scaled_distance_sum(unexpanded_star_map: &Vec<&str>) -> u64 {
 }
 
 // helper fns
-fn expand_star_map(unexpanded_star_map: &Vec<&str>) -> Vec<Vec<String>> {
-  unexpanded_star_map
-    .iter()
-    .map(|s| {
-      s.split("")
-        .filter(|s| s != &"")
-        .map(|s| s.to_owned())
-        .collect_vec()
-    })
-    .collect_vec()
-}
-
-fn find_gaps(star_map: &Vec<Vec<String>>) -> (Vec<i32>, Vec<i32>) {
+fn find_gaps(star_map: &Grid<char>) -> (Vec<i32>, Vec<i32>) {
   let v_gaps = star_map
-    .iter()
+    .rows()
     .enumerate()
-    .filter(|(_, row)| !row.contains(&"#".to_owned()))
+    .filter(|(_, row)| !row.contains(&'#'))
     .map(|(i, _)| i as i32)
     .collect_vec();
 
-  let mut h_gaps: Vec<i32> = Vec::new();
-  for i in 0..star_map[0].len() {
-    if !star_map.iter().map(|row| row[i].clone()).any(|s| s == "#") {
-      h_gaps.push(i as i32);
-    }
-  }
+  let h_gaps = (0..star_map.width())
+    .filter(|i| !star_map.column(*i).any(|ch| ch == &'#'))
+    .map(|i| i as i32)
+    .collect_vec();
 
   return (v_gaps, h_gaps);
 }
 
-fn find_stars(star_map: &Vec<Vec<String>>) -> Vec<(i32, i32)> {
-  let mut stars = Vec::new();
-  for i in 0..star_map.len() {
-    for j in 0..star_map[0].len() {
-      if star_map[i][j] == "#".to_owned() {
-        stars.push((i as i32, j as i32));
-      }
-    }
-  }
-  return stars;
+fn find_stars(star_map: &Grid<char>) -> Vec<(i32, i32)> {
+  return star_map
+    .find_all(&'#')
+    .map(|(row, col)| (row as i32, col as i32))
+    .collect_vec();
 }
 
 fn count_gaps_in_range(gaps: &Vec<i32>, n1: i32, n2: i32) -> i32 {
@@ -1,5 +1,7 @@
 use itertools::Itertools;
 
+use crate::utils::grid::Grid;
+
 /// Day 13, Part 1 -- https://adventofcode.com/2023/day/13
 ///
 /// You've made it to lava island, but it turns out it's REALLY
@@ -35,21 +37,10 @@ use itertools::Itertools;
 /// point total of all patterns in the input?
 #[allow(dead_code)]
 pub fn find_reflection_summary(all_patterns: &Vec<&str>) -> u32 {
-  let mut separated_maps: Vec<Vec<String>> = Vec::new();
-  let mut temp_map: Vec<String> = Vec::new();
-  for line in all_patterns {
-    if line == &"" {
-      separated_maps.push(temp_map.clone());
-      temp_map = Vec::new();
-    } else {
-      temp_map.push(line.to_owned().to_owned())
-    }
-  }
-
   // Check each map and calculate its point value by its inflection point
-  separated_maps
+  separate_patterns(all_patterns)
     .iter()
-    .map(|pattern| find_inflection_point(pattern))
+    .map(|pattern| find_inflection_point(pattern, 0))
     .enumerate()
     .fold(0, |acc, (i, ip)| {
       println!("Pattern {}: {:?}", i, ip);
@@ -69,163 +60,57 @@ pub fn find_reflection_summary(all_patterns: &Vec<&str>) -> u32 {
 ///
 /// What's the new point sum?
 pub fn find_smudged_reflection_summary(all_patterns: &Vec<&str>) -> u32 {
-  let mut separated_maps: Vec<Vec<String>> = Vec::new();
-  let mut temp_map: Vec<String> = Vec::new();
-  for line in all_patterns {
-    if line == &"" {
-      separated_maps.push(temp_map.clone());
-      temp_map = Vec::new();
-    } else {
-      temp_map.push(line.to_owned().to_owned())
-    }
-  }
-
-  let inflection_points = separated_maps
+  separate_patterns(all_patterns)
     .iter()
-    .map(|pattern| find_and_replace_smudge(pattern))
-    .map(|pattern: Vec<String>| find_inflection_point(&pattern))
-    .collect_vec();
-
-  println!("All inflection points found: {:?}", inflection_points);
-
-  inflection_points
-    .iter()
-    .enumerate()
-    .map(|(i, ip)| {
-      println!("Pattern {}: {:?}", i, ip);
-      let v = calculate_value_of_inflection_point(&ip);
-      println!("IP Value: {}", v);
-      v
-    })
+    .map(|pattern| find_inflection_point(pattern, 1))
+    .map(|ip| calculate_value_of_inflection_point(&ip))
     .sum()
 }
 
 // vvv  Helper functions  vvv
-fn find_and_replace_smudge(pattern: &Vec<String>) -> Vec<String> {
-  let mismatch;
-  let opt_mismatch = find_smudge(pattern);
-  if opt_mismatch.is_some() {
-    mismatch = opt_mismatch.unwrap();
-  } else {
-    let pattern_transpose = transpose_pattern(pattern);
-    mismatch = find_smudge(&pattern_transpose).unwrap();
-  }
-
-  println!("Found a mismatch! {:?}", mismatch);
-  let mut new_pattern = pattern.clone();
-  new_pattern[mismatch.row].replace_range(
-    mismatch.col..mismatch.col + 1,
-    if pattern[mismatch.row].chars().nth(mismatch.col).unwrap() == '#' {
-      "."
-    } else {
-      "#"
-    },
-  );
-
-  println!("\nBase pattern:");
-  for line in pattern {
-    println!("{}", line);
-  }
-
-  println!("\nNew pattern:");
-  for line in &new_pattern {
-    println!("{}", line);
-  }
-  return new_pattern;
-}
-
-// Returns a list of all horizontal + vertical inflection points across a pattern
-fn find_inflection_point(pattern: &Vec<String>) -> InflectionPoint {
-  let mut inflection_points: Vec<InflectionPoint> = Vec::new();
-  let pattern_transpose = transpose_pattern(pattern);
-
-  get_inflection_positions(pattern)
-    .iter()
-    .for_each(|pos| inflection_points.push(InflectionPoint::new(ReflectionType::Horizontal, *pos)));
-
-  get_inflection_positions(&pattern_transpose)
-    .iter()
-    .for_each(|pos| inflection_points.push(InflectionPoint::new(ReflectionType::Vertical, *pos)));
-
-  println!("Inflection point found!");
-  inflection_points[0]
+fn separate_patterns(all_patterns: &Vec<&str>) -> Vec<Grid<char>> {
+  all_patterns
+    .split(|line| line.is_empty())
+    .filter(|lines| !lines.is_empty())
+    .map(Grid::parse)
+    .collect_vec()
 }
 
-// Returns any horizontal inflection point across a given pattern
-fn get_inflection_positions(pattern: &Vec<String>) -> Vec<usize> {
-  let mut inflection_points: Vec<usize> = Vec::new();
-  for i in 1..pattern[0].len() {
-    let max_width = pattern[0].len() / 2;
-    if pattern.iter().all(|s| {
-      let s1 = if i <= max_width { &s[0..i] } else { &s[i..] };
-      let s2 = if i <= max_width {
-        &s[i..i * 2]
-      } else {
-        &s[s.len() - (s1.len() * 2)..s.len() - s1.len()]
-      };
-      s1 == s2.chars().rev().collect::<String>()
-    }) {
-      inflection_points.push(i)
-    }
-  }
-  return inflection_points;
+// Finds the reflection line of a pattern that has exactly `smudges` cells
+// that don't match their mirrored cell, checking columns before rows
+fn find_inflection_point(pattern: &Grid<char>, smudges: usize) -> InflectionPoint {
+  let horizontal = get_inflection_positions(pattern, smudges)
+    .into_iter()
+    .map(|pos| InflectionPoint::new(ReflectionType::Horizontal, pos));
+  let vertical = get_inflection_positions(&pattern.transpose(), smudges)
+    .into_iter()
+    .map(|pos| InflectionPoint::new(ReflectionType::Vertical, pos));
+
+  horizontal
+    .chain(vertical)
+    .next()
+    .unwrap_or_else(|| panic!("No inflection point found for pattern:\n{}", pattern))
 }
 
-// Returns any horizontal inflection point across a given pattern
-fn find_smudge(pattern: &Vec<String>) -> Option<Mismatch> {
-  for i in 1..pattern[0].len() {
-    let mut mismatches: Vec<Mismatch> = Vec::new();
-    let max_width = pattern[0].len() / 2;
-    for s in pattern {
-      let s1 = if i <= max_width { &s[0..i] } else { &s[i..] };
-      let s2: &str = if i <= max_width {
-        &s[i..i * 2]
-      } else {
-        &s[s.len() - (s1.len() * 2)..s.len() - s1.len()]
-      };
-      for j in 0..s1.len() {
-        if s1.chars().nth(j) != s2.chars().rev().nth(j) {
-          mismatches.push(Mismatch::new(i - 1, j))
-        }
-      }
-    }
-
-    // println!("  Mismatches found: {:?}", mismatches);
-    if mismatches.len() == 1 {
-      println!("Mismatch in reflection: {:?}", mismatches[0]);
-      return Some(mismatches[0]);
-    }
-  }
-
-  return None;
+// Returns every column a pattern reflects across with exactly `smudges` mismatches
+fn get_inflection_positions(pattern: &Grid<char>, smudges: usize) -> Vec<usize> {
+  (1..pattern.width())
+    .filter(|i| count_mismatches(pattern, *i) == smudges)
+    .collect_vec()
 }
 
-// Transposes a given 2D matrix (via StackOverflow):
-// https://stackoverflow.com/questions/64498617/how-to-transpose-a-vector-of-vectors-in-rust)
-fn transpose<T>(v: Vec<Vec<T>>) -> Vec<Vec<T>> {
-  assert!(!v.is_empty());
-  let len = v[0].len();
-  let mut iters: Vec<_> = v.into_iter().map(|n| n.into_iter()).collect();
-  (0..len)
-    .map(|_| {
-      iters
-        .iter_mut()
-        .map(|n| n.next().unwrap())
-        .collect::<Vec<T>>()
+// Counts cells that differ from their reflection across the line left of column `i`
+fn count_mismatches(pattern: &Grid<char>, i: usize) -> usize {
+  let span = i.min(pattern.width() - i);
+  (0..span)
+    .map(|offset| {
+      pattern
+        .column(i - 1 - offset)
+        .zip(pattern.column(i + offset))
+        .filter(|(left, right)| left != right)
+        .count()
     })
-    .collect()
-}
-
-fn transpose_pattern(pattern: &Vec<String>) -> Vec<String> {
-  let pattern_2d = pattern
-    .iter()
-    .map(|s| s.chars().map(|ch| ch.to_string()).collect_vec())
-    .collect_vec();
-
-  transpose(pattern_2d)
-    .iter()
-    .map(|v| v.join(""))
-    .collect_vec()
+    .sum()
 }
 
 // Calculates the point value of a given inflection point
@@ -254,14 +139,17 @@ impl InflectionPoint {
   }
 }
 
-#[derive(Debug, Copy, Clone)]
-struct Mismatch {
-  row: usize,
-  col: usize,
-}
-
-impl Mismatch {
-  pub fn new(row: usize, col: usize) -> Mismatch {
-    Mismatch { row, col }
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn smudge_moves_the_reflection_line() {
+    let patterns = vec![
+      "#.##..##.", "..#.##.#.", "##......#", "##......#", "..#.##.#.", "..##..##.", "#.#.##.#.", "",
+      "#...##..#", "#....#..#", "..##..###", "#####.##.", "#####.##.", "..##..###", "#....#..#",
+    ];
+    assert_eq!(find_reflection_summary(&patterns), 405);
+    assert_eq!(find_smudged_reflection_summary(&patterns), 400);
   }
 }
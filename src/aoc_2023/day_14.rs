@@ -1,10 +1,10 @@
-use itertools::Itertools;
-use std::{cmp::Ordering, collections::HashMap};
-
-const NORTH: (isize, isize) = (-1, 0);
-const SOUTH: (isize, isize) = (1, 0);
-const EAST: (isize, isize) = (0, 1);
-const WEST: (isize, isize) = (0, -1);
+use crate::utils::{
+  animation::{Cell, Color, Frame},
+  bitgrid::BitGrid,
+  cycle,
+  geometry::Direction,
+  grid::Grid,
+};
 
 /// Day 14, Part 1 -- https://adventofcode.com/2023/day/14
 ///
@@ -39,18 +39,9 @@ const WEST: (isize, isize) = (0, -1);
 /// its own row. What's the total load on the north support beam
 /// after tilting the array to roll the loose rocks north?
 pub fn calculate_north_load(initial_map: &Vec<&str>) -> u32 {
-  let map_vec = initial_map
-    .iter()
-    .map(|row| {
-      row
-        .split("")
-        .filter(|s| s != &"")
-        .map(|s| s.to_owned())
-        .collect_vec()
-    })
-    .collect_vec();
-
-  return calculate_load(&shift_rocks(&map_vec, NORTH));
+  let (rocks, walls) = parse_platform(initial_map);
+
+  return calculate_load(&shift_rocks(&rocks, &walls, Direction::North));
 }
 
 /// Day 14, Part 2 -- https://adventofcode.com/2023/day/14#part2
@@ -65,184 +56,116 @@ pub fn calculate_north_load(initial_map: &Vec<&str>) -> u32 {
 /// What's the total load on the north support beams after running
 /// the spin cycle 1,000,000,000 (1 billion) times?
 pub fn calculate_north_load_after_1b_cycles(initial_map: &Vec<&str>) -> u32 {
-  let map_vec = initial_map
-    .iter()
-    .map(|row| {
-      row
-        .split("")
-        .filter(|s| s != &"")
-        .map(|s| s.to_owned())
-        .collect_vec()
-    })
-    .collect_vec();
-
-  let spin_cycle_directions = vec![NORTH, WEST, SOUTH, EAST];
-  let mut hashmap: HashMap<Vec<Vec<String>>, usize> = HashMap::new();
-  let mut shifted_map = map_vec.clone();
-
-  for i in 0..1_000_000_000 {
-    for direction in &spin_cycle_directions {
-      shifted_map = shift_rocks(&shifted_map, *direction)
-    }
+  let (rocks, walls) = parse_platform(initial_map);
 
-    if hashmap.contains_key(&shifted_map) {
-      let initial_index: usize = *hashmap.get(&shifted_map).unwrap();
-      let cycle_length = i - initial_index;
-      let remaining = (1_000_000_000 - i) % cycle_length;
-      let out_index = initial_index + ((i - initial_index + remaining) % cycle_length) - 1;
-      shifted_map = hashmap
-        .clone()
-        .iter()
-        .find(|(_k, v)| v == &&out_index)
-        .unwrap()
-        .0
-        .to_owned();
-
-      break;
-    } else {
-      hashmap.insert(shifted_map.clone(), i);
-    }
-  }
+  let spun_rocks = cycle::state_at(&rocks, |rocks| spin_cycle(rocks, &walls), 1_000_000_000);
 
-  return calculate_load(&shifted_map);
+  return calculate_load(&spun_rocks);
 }
 
-/* --- Helper functions --- */
-fn shift_rocks(initial_map: &Vec<Vec<String>>, direction: (isize, isize)) -> Vec<Vec<String>> {
-  let mut map: Vec<Vec<String>> = initial_map
-    .clone()
-    .iter()
-    .map(|row| row.iter().map(|s| s.to_owned().to_owned()).collect_vec())
-    .collect_vec();
-
-  let mut movable_rocks: Vec<(isize, isize)> = Vec::new();
-  for row in 0..initial_map.len() {
-    for col in 0..initial_map[0].len() {
-      if initial_map[row][col] == "O" {
-        movable_rocks.push((row as isize, col as isize));
-        map[row][col] = ".".to_owned();
+/// Visualisation for day 14: the first few spin cycles with every rock
+/// rolling one tile per frame.
+pub fn spin_cycle_frames(initial_map: &Vec<&str>) -> Vec<Frame> {
+  let (mut rocks, walls) = parse_platform(initial_map);
+
+  let mut frames = vec![platform_frame(&rocks, &walls, format!("start, load {}", calculate_load(&rocks)))];
+  for cycle in 1..=3 {
+    for direction in [Direction::North, Direction::West, Direction::South, Direction::East] {
+      let mut step = 0;
+      while let Some(rolled) = roll_step(&rocks, &walls, direction) {
+        rocks = rolled;
+        step += 1;
+        let caption = format!(
+          "cycle {}, tilt {:?}, step {}, load {}",
+          cycle,
+          direction,
+          step,
+          calculate_load(&rocks)
+        );
+        frames.push(platform_frame(&rocks, &walls, caption));
       }
     }
   }
+  frames
+}
 
-  movable_rocks.sort_by(|r1, r2| compare_rocks(r1, r2, direction));
-  for rock in movable_rocks {
-    let new_pos = shift_rock(rock, &map, direction);
-    map[new_pos.0 as usize][new_pos.1 as usize] = "O".to_owned();
-  }
+/* --- Helper functions --- */
 
-  return map;
+// Splits the map into the rocks that roll (O) and the ones that don't (#)
+fn parse_platform(initial_map: &[&str]) -> (BitGrid, BitGrid) {
+  let map = Grid::parse(initial_map);
+  (BitGrid::from_grid(&map, |&c| c == 'O'), BitGrid::from_grid(&map, |&c| c == '#'))
 }
 
-fn shift_rock(
-  initial_rock_pos: (isize, isize),
-  map: &Vec<Vec<String>>,
-  direction: (isize, isize),
-) -> (isize, isize) {
-  match direction {
-    NORTH => {
-      if initial_rock_pos.0 == 0 {
-        return initial_rock_pos;
-      }
-    }
-    SOUTH => {
-      if initial_rock_pos.0 == (map.len() - 1) as isize {
-        return initial_rock_pos;
-      }
-    }
-    WEST => {
-      if initial_rock_pos.1 == 0 {
-        return initial_rock_pos;
-      }
-    }
-    EAST => {
-      if initial_rock_pos.1 == (map[0].len() - 1) as isize {
-        return initial_rock_pos;
-      }
-    }
-    _ => panic!("Invalid direction passed"),
-  }
+fn spin_cycle(rocks: &BitGrid, walls: &BitGrid) -> BitGrid {
+  [Direction::North, Direction::West, Direction::South, Direction::East]
+    .into_iter()
+    .fold(rocks.clone(), |shifted, direction| shift_rocks(&shifted, walls, direction))
+}
 
-  let mut pos = initial_rock_pos.clone();
-  while next_pos_in_bounds(pos, map, direction) {
-    let to_check = (
-      (pos.0 + direction.0) as usize,
-      (pos.1 + direction.1) as usize,
-    );
-    if &map[to_check.0][to_check.1] == "O" || &map[to_check.0][to_check.1] == "#" {
-      break;
-    } else {
-      pos.0 += direction.0;
-      pos.1 += direction.1;
-    }
+// Moves every rock that has space one step at a time until none can move
+fn shift_rocks(rocks: &BitGrid, walls: &BitGrid, direction: Direction) -> BitGrid {
+  let mut rocks = rocks.clone();
+  while let Some(rolled) = roll_step(&rocks, walls, direction) {
+    rocks = rolled;
   }
-  return pos;
+  rocks
 }
 
-fn next_pos_in_bounds(
-  pos: (isize, isize),
-  map: &Vec<Vec<String>>,
-  direction: (isize, isize),
-) -> bool {
-  match direction {
-    NORTH => pos.0 > 0,
-    SOUTH => pos.0 < (map.len() - 1) as isize,
-    WEST => pos.1 > 0,
-    EAST => pos.1 < (map[0].len() - 1) as isize,
-    _ => panic!("Invalid direction passed"),
+// Moves every rock with an empty tile in front of it, or None if none can
+fn roll_step(rocks: &BitGrid, walls: &BitGrid, direction: Direction) -> Option<BitGrid> {
+  let blocked = rocks.clone() | walls.clone();
+  let moved = rocks.shifted(direction) & !blocked;
+  if moved.is_empty() {
+    return None;
   }
+  Some((rocks.clone() & !moved.shifted(direction.reverse())) | moved)
 }
 
-fn compare_rocks(r1: &(isize, isize), r2: &(isize, isize), direction: (isize, isize)) -> Ordering {
-  match direction {
-    NORTH => {
-      if r1.0 > r2.0 {
-        return Ordering::Greater;
-      };
-      if r1.0 < r2.0 {
-        return Ordering::Less;
-      };
-      Ordering::Equal
-    }
-    SOUTH => {
-      if r1.0 < r2.0 {
-        return Ordering::Greater;
-      };
-      if r1.0 > r2.0 {
-        return Ordering::Less;
-      };
-      Ordering::Equal
-    }
-    WEST => {
-      if r1.1 > r2.1 {
-        return Ordering::Greater;
-      };
-      if r1.1 < r2.1 {
-        return Ordering::Less;
-      };
-      Ordering::Equal
-    }
-    EAST => {
-      if r1.1 < r2.1 {
-        return Ordering::Greater;
-      };
-      if r1.1 > r2.1 {
-        return Ordering::Less;
-      };
-      Ordering::Equal
+fn platform_frame(rocks: &BitGrid, walls: &BitGrid, caption: String) -> Frame {
+  let platform = Grid::new(rocks.width(), rocks.height(), ());
+  Frame::from_grid(&platform, caption, |pos, _| {
+    if rocks.get(pos) {
+      Cell::colored('O', Color::Yellow)
+    } else if walls.get(pos) {
+      Cell::colored('#', Color::Grey)
+    } else {
+      Cell::plain('.')
     }
-    _ => panic!("Invalid direction passed"),
-  }
+  })
 }
 
-fn calculate_load(map: &Vec<Vec<String>>) -> u32 {
-  let mut load: u32 = 0;
-  for row in 0..map.len() {
-    for col in 0..map[0].len() {
-      if map[row][col] == "O" {
-        load += (map.len() - row) as u32;
-      }
-    }
+fn calculate_load(rocks: &BitGrid) -> u32 {
+  rocks
+    .ones()
+    .map(|(row, _)| (rocks.height() - row as usize) as u32)
+    .sum()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::utils::snapshot::assert_snapshot;
+
+  #[test]
+  fn tilt_north_snapshot() {
+    let (rocks, walls) = parse_platform(&[
+      "O....#....",
+      "O.OO#....#",
+      ".....##...",
+      "OO.#O....O",
+      ".O.....O#.",
+      "O.#..O.#.#",
+      "..O..#O..O",
+      ".......O..",
+      "#....###..",
+      "#OO..#....",
+    ]);
+
+    let tilted = shift_rocks(&rocks, &walls, Direction::North);
+    let mut map = Grid::new(rocks.width(), rocks.height(), '.');
+    tilted.ones().for_each(|pos| map[pos] = 'O');
+    walls.ones().for_each(|pos| map[pos] = '#');
+    assert_snapshot!("day_14_tilt_north", map.to_string());
   }
-  return load;
 }
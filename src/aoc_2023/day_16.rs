@@ -1,67 +1,54 @@
 use std::collections::HashSet;
 
-use itertools::Itertools;
-const NORTH: Vector2D = (-1, 0);
-const SOUTH: Vector2D = (1, 0);
-const EAST: Vector2D = (0, 1);
-const WEST: Vector2D = (0, -1);
+use crate::utils::{
+  animation::{Cell, Color, Frame},
+  geometry::{
+    Direction::{self, East, North, South, West},
+    Vec2,
+  },
+  grid::Grid,
+};
 
 /// Day 16, Part 1 -- https://adventofcode.com/2023/day/16
 ///
 /// Prompt is very large, see markdown file:
 /// src\aoc_2023\prompts\day_17.md
 pub fn find_energized_tiles(floor_map: &Vec<&str>) -> usize {
-  let map = floor_map
-    .iter()
-    .map(|s| {
-      s.split("")
-        .map(|s| s.to_owned())
-        .filter(|s| s != "")
-        .collect_vec()
-    })
-    .collect_vec();
-
-  let pos = (0, 0);
-  match map[pos.0 as usize][pos.1 as usize].as_str() {
-    "." | "-" => return explore(vec![Beam::new(pos, EAST)], &map),
-    r"\" | "|" => return explore(vec![Beam::new(pos, SOUTH)], &map),
+  let map = Grid::parse(floor_map);
+
+  let pos = Vec2::ZERO;
+  match map[pos] {
+    '.' | '-' => return explore(vec![Beam::new(pos, East)], &map),
+    '\\' | '|' => return explore(vec![Beam::new(pos, South)], &map),
     _ => panic!("Unaccounted for!"),
   }
 }
 
 /// Day 16, Part 2
 pub fn find_max_energized_tiles(floor_map: &Vec<&str>) -> usize {
-  let map = floor_map
-    .iter()
-    .map(|s| {
-      s.split("")
-        .map(|s| s.to_owned())
-        .filter(|s| s != "")
-        .collect_vec()
-    })
-    .collect_vec();
-
-  let mut start_positions: Vec<Vector2D> = Vec::new();
-  for i in 0..map[0].len() {
-    start_positions.push((0, i as isize));
-    start_positions.push((map.len() as isize - 1, i as isize));
+  let map = Grid::parse(floor_map);
+
+  let mut start_positions: Vec<Vec2> = Vec::new();
+  for i in 0..map.width() {
+    start_positions.push(Vec2::new(i as isize, 0));
+    start_positions.push(Vec2::new(i as isize, map.height() as isize - 1));
   }
 
   let values = start_positions.iter().map(|pos| {
-    if pos.0 == 0 {
-      match map[pos.0 as usize][pos.1 as usize].as_str() {
-        "." | "|" => return explore(vec![Beam::new(*pos, SOUTH)], &map),
-        "/" => return explore(vec![Beam::new(*pos, WEST)], &map),
-        r"\" => return explore(vec![Beam::new(*pos, EAST)], &map),
-        "-" => return explore(vec![Beam::new(*pos, EAST), Beam::new(*pos, WEST)], &map),
+    if pos.y == 0 {
+      match map[*pos] {
+        '.' | '|' => return explore(vec![Beam::new(*pos, South)], &map),
+        '/' => return explore(vec![Beam::new(*pos, West)], &map),
+        '\\' => return explore(vec![Beam::new(*pos, East)], &map),
+        '-' => return explore(vec![Beam::new(*pos, East), Beam::new(*pos, West)], &map),
         _ => panic!("Unaccounted for!"),
       }
     } else {
-      match map[pos.0 as usize][pos.1 as usize].as_str() {
-        "." | "|" => return explore(vec![Beam::new(*pos, NORTH)], &map),
-        "/" => return explore(vec![Beam::new(*pos, EAST)], &map),
-        r"\" => return explore(vec![Beam::new(*pos, WEST)], &map),
-        "-" => return explore(vec![Beam::new(*pos, EAST), Beam::new(*pos, WEST)], &map),
+      match map[*pos] {
+        '.' | '|' => return explore(vec![Beam::new(*pos, North)], &map),
+        '/' => return explore(vec![Beam::new(*pos, East)], &map),
+        '\\' => return explore(vec![Beam::new(*pos, West)], &map),
+        '-' => return explore(vec![Beam::new(*pos, East), Beam::new(*pos, West)], &map),
         _ => panic!("Unaccounted for setup!"),
       }
     }
@@ -70,11 +57,58 @@ pub fn find_max_energized_tiles(floor_map: &Vec<&str>) -> usize {
   return values.max().unwrap();
 }
 
-fn explore(starting_arr: Vec<Beam>, passed_map: &Vec<Vec<String>>) -> usize {
-  let mut explored: HashSet<(Vector2D, Vector2D)> = HashSet::new();
+fn explore(starting_arr: Vec<Beam>, passed_map: &Grid<char>) -> usize {
+  energize(starting_arr, passed_map).len()
+}
+
+/// Visualisation for day 16: the part 1 beam spreading across the floor,
+/// with the most recently energized tiles in red.
+pub fn beam_frames(floor_map: &Vec<&str>) -> Vec<Frame> {
+  const MAX_FRAMES: usize = 300;
+
+  let map = Grid::parse(floor_map);
+  let mut visits: Vec<Vec2> = Vec::new();
+  energize_observed(vec![Beam::new(Vec2::ZERO, East)], &map, |beam| visits.push(beam.pos));
+
+  let stride = visits.len().div_ceil(MAX_FRAMES).max(1);
+  let mut energized = map.map(|_| false);
+  let mut frames = Vec::new();
+  for (i, chunk) in visits.chunks(stride).enumerate() {
+    chunk.iter().for_each(|pos| energized[*pos] = true);
+    let steps = i * stride + chunk.len();
+    let caption = format!("step {}, {} tiles energized", steps, energized.find_all(&true).count());
+    frames.push(Frame::from_grid(&map, caption, |pos, &ch| {
+      let shown = if ch == '.' { '#' } else { ch };
+      if chunk.contains(&Vec2::from(pos)) {
+        Cell::colored(shown, Color::Red)
+      } else if energized[pos] {
+        Cell::colored(shown, Color::Yellow)
+      } else if ch == '.' {
+        Cell::plain(ch)
+      } else {
+        Cell::colored(ch, Color::Cyan)
+      }
+    }));
+  }
+  frames
+}
+
+fn energize(starting_arr: Vec<Beam>, passed_map: &Grid<char>) -> HashSet<Vec2> {
+  energize_observed(starting_arr, passed_map, |_| {})
+}
+
+// Same as energize, but hands every newly explored beam state to `observe`
+// in the order the beams reach them
+fn energize_observed(
+  starting_arr: Vec<Beam>,
+  passed_map: &Grid<char>,
+  mut observe: impl FnMut(&Beam),
+) -> HashSet<Vec2> {
+  let mut explored: HashSet<(Vec2, Direction)> = HashSet::new();
   let mut unexplored_beams: Vec<Beam> = starting_arr;
   let mut current = unexplored_beams[0];
   explored.insert((current.pos, current.dir));
+  observe(&current);
 
   loop {
     let starting_pos = current.pos;
@@ -102,94 +136,84 @@ fn explore(starting_arr: Vec<Beam>, passed_map: &Vec<Vec<String>>) -> usize {
     }
 
     explored.insert((current.pos, current.dir));
+    observe(&current);
   }
 
-  explored
-    .iter()
-    .map(|ex| ex.0)
-    .collect::<HashSet<Vector2D>>()
-    .into_iter()
-    .collect_vec()
-    .len()
-}
-
-type Vector2D = (isize, isize);
-fn v_sum(v1: Vector2D, v2: Vector2D) -> Vector2D {
-  (v1.0 + v2.0, v1.1 + v2.1)
+  explored.iter().map(|ex| ex.0).collect::<HashSet<Vec2>>()
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct Beam {
-  pos: Vector2D,
-  dir: Vector2D,
+  pos: Vec2,
+  dir: Direction,
 }
 
 impl Beam {
-  pub fn new(pos: Vector2D, dir: Vector2D) -> Beam {
+  pub fn new(pos: Vec2, dir: Direction) -> Beam {
     Beam { pos, dir }
   }
 
-  pub fn next(&mut self, map: &Vec<Vec<String>>) -> Option<Beam> {
-    let next_pos = v_sum(self.dir, self.pos);
-    let next_ch = map[next_pos.0 as usize][next_pos.1 as usize].as_str();
+  pub fn next(&mut self, map: &Grid<char>) -> Option<Beam> {
+    let next_pos = self.pos + self.dir;
+    let next_ch = map[next_pos];
     match (self.dir, next_ch) {
-      (NORTH, ".") | (SOUTH, ".") | (EAST, ".") | (WEST, ".") => self.pos = next_pos,
-      (NORTH, "|") | (SOUTH, "|") | (EAST, "-") | (WEST, "-") => self.pos = next_pos,
+      (North, '.') | (South, '.') | (East, '.') | (West, '.') => self.pos = next_pos,
+      (North, '|') | (South, '|') | (East, '-') | (West, '-') => self.pos = next_pos,
 
-      (NORTH, "/") => {
+      (North, '/') => {
         self.pos = next_pos;
-        self.dir = EAST;
+        self.dir = East;
       }
-      (NORTH, r"\") => {
+      (North, '\\') => {
         self.pos = next_pos;
-        self.dir = WEST;
+        self.dir = West;
       }
-      (NORTH, "-") => {
+      (North, '-') => {
         self.pos = next_pos;
-        self.dir = EAST;
-        return Some(Beam::new(next_pos, WEST));
+        self.dir = East;
+        return Some(Beam::new(next_pos, West));
       }
 
-      (SOUTH, "/") => {
+      (South, '/') => {
         self.pos = next_pos;
-        self.dir = WEST;
+        self.dir = West;
       }
-      (SOUTH, r"\") => {
+      (South, '\\') => {
         self.pos = next_pos;
-        self.dir = EAST;
+        self.dir = East;
       }
-      (SOUTH, "-") => {
+      (South, '-') => {
         self.pos = next_pos;
-        self.dir = EAST;
-        return Some(Beam::new(next_pos, WEST));
+        self.dir = East;
+        return Some(Beam::new(next_pos, West));
       }
 
-      (EAST, "/") => {
+      (East, '/') => {
         self.pos = next_pos;
-        self.dir = NORTH;
+        self.dir = North;
       }
-      (EAST, r"\") => {
+      (East, '\\') => {
         self.pos = next_pos;
-        self.dir = SOUTH;
+        self.dir = South;
       }
-      (EAST, "|") => {
+      (East, '|') => {
         self.pos = next_pos;
-        self.dir = NORTH;
-        return Some(Beam::new(next_pos, SOUTH));
+        self.dir = North;
+        return Some(Beam::new(next_pos, South));
       }
 
-      (WEST, "/") => {
+      (West, '/') => {
         self.pos = next_pos;
-        self.dir = SOUTH;
+        self.dir = South;
       }
-      (WEST, r"\") => {
+      (West, '\\') => {
         self.pos = next_pos;
-        self.dir = NORTH;
+        self.dir = North;
       }
-      (WEST, "|") => {
+      (West, '|') => {
         self.pos = next_pos;
-        self.dir = NORTH;
-        return Some(Beam::new(next_pos, SOUTH));
+        self.dir = North;
+        return Some(Beam::new(next_pos, South));
       }
 
       _ => println!(
@@ -202,16 +226,34 @@ impl Beam {
   }
 }
 
-pub fn next_pos_valid(pos: Vector2D, dir: Vector2D, map: &Vec<Vec<String>>) -> bool {
-  if pos.0 < 0 || pos.1 < 0 || pos.0 >= map.len() as isize || pos.1 >= map[0].len() as isize {
-    return false;
-  }
+pub fn next_pos_valid(pos: Vec2, dir: Direction, map: &Grid<char>) -> bool {
+  map.in_bounds(pos) && map.in_bounds(pos + dir)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::utils::snapshot::assert_snapshot;
+
+  #[test]
+  fn energized_tiles_snapshot() {
+    let map = Grid::parse(&[
+      r".|...\....",
+      r"|.-.\.....",
+      r".....|-...",
+      r"........|.",
+      r"..........",
+      r".........\",
+      r"..../.\\..",
+      r".-.-/..|..",
+      r".|....-|.\",
+      r"..//.|....",
+    ]);
+
+    let energized = energize(vec![Beam::new(Vec2::ZERO, East)], &map);
+    let mut grid = map.map(|_| '.');
+    energized.iter().for_each(|pos| grid[*pos] = '#');
 
-  match dir {
-    NORTH => pos.0 >= 1,
-    SOUTH => pos.0 != map.len() as isize - 1,
-    EAST => pos.1 != map[0].len() as isize - 1,
-    WEST => pos.1 >= 1,
-    _ => panic!("Invalid direction: {:?}", dir),
+    assert_snapshot!("day_16_energized", grid.to_string());
   }
 }
@@ -0,0 +1,103 @@
+use crate::utils::{
+  geometry::{Direction, Vec2},
+  grid::Grid,
+  search,
+};
+
+/// Day 17, Part 1 -- https://adventofcode.com/2023/day/17
+///
+/// Prompt is very large, see markdown file:
+/// src\aoc_2023\prompts\day_17.md
+///
+/// Move a crucible from the top-left block to the bottom-right block
+/// of the city, losing the heat marked on each block it enters. It
+/// can't reverse and can go at most three blocks in a straight line
+/// before turning. What's the least heat loss it can incur?
+pub fn find_least_heat_loss(city_map: &Vec<&str>) -> usize {
+  least_heat_loss(&parse_city(city_map), 1, 3)
+}
+
+/// Day 17, Part 2
+///
+/// Ultra crucibles have to move at least four blocks in a straight line
+/// before turning (or stopping at the end) but can go up to ten. What's
+/// the least heat loss an ultra crucible can incur?
+pub fn find_least_ultra_heat_loss(city_map: &Vec<&str>) -> usize {
+  least_heat_loss(&parse_city(city_map), 4, 10)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Crucible {
+  pos: Vec2,
+  dir: Direction,
+  run: usize,
+}
+
+// Helper functions
+fn parse_city(city_map: &Vec<&str>) -> Grid<usize> {
+  Grid::parse_with(city_map, |ch| {
+    ch.to_digit(10).unwrap_or_else(|| panic!("Invalid heat loss value: {}", ch)) as usize
+  })
+}
+
+fn least_heat_loss(city: &Grid<usize>, min_run: usize, max_run: usize) -> usize {
+  let end = Vec2::new(city.width() as isize - 1, city.height() as isize - 1);
+  let starts = [Direction::East, Direction::South].map(|dir| Crucible {
+    pos: Vec2::ZERO,
+    dir,
+    run: 0,
+  });
+
+  let moves = |crucible: &Crucible| {
+    let crucible = *crucible;
+    [crucible.dir, crucible.dir.turn_left(), crucible.dir.turn_right()]
+      .into_iter()
+      .filter(move |dir| match *dir == crucible.dir {
+        true => crucible.run < max_run,
+        false => crucible.run >= min_run,
+      })
+      .filter_map(move |dir| {
+        let pos = crucible.pos + dir;
+        let run = if dir == crucible.dir { crucible.run + 1 } else { 1 };
+        city.get(pos).map(|heat| (Crucible { pos, dir, run }, *heat))
+      })
+  };
+
+  search::astar(
+    starts,
+    moves,
+    |crucible| crucible.pos.manhattan(end),
+    |crucible| crucible.pos == end && crucible.run >= min_run,
+  )
+  .expect("No route found to the factory.")
+  .cost
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn example_heat_loss() {
+    let city = vec![
+      "2413432311323",
+      "3215453535623",
+      "3255245654254",
+      "3446585845452",
+      "4546657867536",
+      "1438598798454",
+      "4457876987766",
+      "3637877979653",
+      "4654967986887",
+      "4564679986453",
+      "1224686865563",
+      "2546548887735",
+      "4322674655533",
+    ];
+    assert_eq!(find_least_heat_loss(&city), 102);
+    assert_eq!(find_least_ultra_heat_loss(&city), 94);
+
+    let unfortunate = vec!["111111111111", "999999999991", "999999999991", "999999999991", "999999999991"];
+    assert_eq!(find_least_ultra_heat_loss(&unfortunate), 71);
+  }
+}
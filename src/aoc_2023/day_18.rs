@@ -1,15 +1,12 @@
-use std::collections::HashSet;
-
 use itertools::Itertools;
 
-type Vector2D = (isize, isize);
-const NORTH: Vector2D = (-1, 0);
-const SOUTH: Vector2D = (1, 0);
-const EAST: Vector2D = (0, 1);
-const WEST: Vector2D = (0, -1);
-fn v_add(v1: Vector2D, v2: Vector2D) -> Vector2D {
-  (v1.0 + v2.0, v1.1 + v2.1)
-}
+use crate::utils::{
+  geometry::{Direction, Vec2},
+  image::{self, Image, Rgb},
+  parse,
+  polygon::Polygon,
+  sparse_grid::SparseGrid,
+};
 
 /// Day 18, Part 1 -- https://adventofcode.com/2023/day/18
 ///
@@ -50,60 +47,103 @@ fn v_add(v1: Vector2D, v2: Vector2D) -> Vector2D {
 /// 1m^2 of space for lava storage, how many m^2 of space are emptied
 /// according to the final input?
 pub fn calculate_lava_volume(dig_plan: &Vec<&str>) -> usize {
-  let mut edge_set: HashSet<Vector2D> = HashSet::new();
-  let mut current_pos: Vector2D = (0, 0);
-  edge_set.insert(current_pos);
-
-  for instruction in dig_plan {
-    let cleaned_instructions = instruction
-      .chars()
-      .filter(|ch| ch != &'(' && ch != &')')
-      .join("");
+  let polygon = dig_polygon(dig_plan, parse_dig_step);
+  image::debug_dump("2023_day_18_outline", || outline_image(&polygon));
+  polygon.lattice_points()
+}
 
-    let (dir, distance, _color) = cleaned_instructions
-      .split(" ")
-      .filter(|s| s != &"")
-      .collect_tuple()
-      .unwrap();
+/// Day 18, Part 2
+///
+/// The elves mixed up the instructions, the colors are the real ones!
+/// Each hex code is a five digit hex distance followed by a direction
+/// digit (0 = R, 1 = D, 2 = L, 3 = U), so #70c710 means R 461937. The
+/// lagoon is far too big to dig out cell by cell now. How many m^2 of
+/// lava can it hold?
+pub fn calculate_hex_lava_volume(dig_plan: &Vec<&str>) -> usize {
+  dig_polygon(dig_plan, parse_hex_dig_step).lattice_points()
+}
 
-    let current_dir = match dir {
-      "U" => NORTH,
-      "D" => SOUTH,
-      "L" => WEST,
-      "R" => EAST,
-      _ => panic!("Invalid direction argument passed: {}", dir),
-    };
+// Helpers
+// The trench is one cell wide, so treating each cell as a lattice point
+// makes the dug out area every point inside or on the polygon
+fn dig_polygon(dig_plan: &[&str], parse_step: fn(&str) -> (Direction, isize)) -> Polygon {
+  let steps = dig_plan.iter().filter(|line| !line.trim().is_empty()).map(|line| parse_step(line));
+  Polygon::from_steps(Vec2::ZERO, steps)
+}
 
-    for _ in 0..distance.parse::<usize>().unwrap() {
-      current_pos = v_add(current_pos, current_dir);
-      edge_set.insert(current_pos);
+// Every tile the trench runs through
+fn trench(polygon: &Polygon) -> SparseGrid<bool> {
+  let mut trench = SparseGrid::new(false);
+  for (&from, &to) in polygon.vertices().iter().circular_tuple_windows() {
+    let step = (to - from).signum();
+    let mut pos = from;
+    while pos != to {
+      trench.insert(pos, true);
+      pos += step;
     }
   }
+  trench
+}
 
-  let mut to_explore: Vec<Vector2D> = vec![(1, 1)];
-  let mut explored: Vec<Vector2D> = Vec::from_iter(edge_set.iter().map(|v| *v));
+fn outline_image(polygon: &Polygon) -> Image {
+  Image::from_grid(&trench(polygon).to_grid(), 4, |&dug| if dug { Rgb::RED } else { Rgb::DARK })
+}
 
-  while to_explore.len() > 0 {
-    let current = to_explore.pop().unwrap();
+fn parse_dig_step(instruction: &str) -> (Direction, isize) {
+  let (dir, distance, _color) = scan_instruction(instruction);
+  let dir = dir.parse::<Direction>().unwrap_or_else(|e| panic!("{}", e));
+  (dir, distance.parse().unwrap())
+}
 
-    vec![
-      v_add(current, NORTH),
-      v_add(current, SOUTH),
-      v_add(current, EAST),
-      v_add(current, WEST),
-    ]
-    .iter()
-    .for_each(|pos| {
-      if !to_explore.contains(pos) && !explored.contains(pos) {
-        to_explore.push(*pos);
-        if explored.len() % 100 == 0 {
-          println!("Explored: {}", explored.len())
-        }
-      }
-    });
+fn parse_hex_dig_step(instruction: &str) -> (Direction, isize) {
+  let (_dir, _distance, color) = scan_instruction(instruction);
+  let distance = isize::from_str_radix(&color[..5], 16).unwrap();
+  let dir = match &color[5..] {
+    "0" => Direction::East,
+    "1" => Direction::South,
+    "2" => Direction::West,
+    "3" => Direction::North,
+    _ => panic!("Invalid direction digit in color: #{}", color),
+  };
+  (dir, distance)
+}
 
-    explored.push(current);
+// Splits "R 6 (#70c710)" into its parts
+fn scan_instruction(instruction: &str) -> (&str, &str, &str) {
+  match parse::scan("{} {} (#{})", instruction.trim()).as_deref() {
+    Some(&[dir, distance, color]) => (dir, distance, color),
+    _ => panic!("Invalid dig instruction: {}", instruction),
   }
+}
 
-  explored.len()
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::utils::snapshot::assert_snapshot;
+
+  #[test]
+  fn dig_outline_snapshot() {
+    let dig_plan = vec![
+      "R 6 (#70c710)",
+      "D 5 (#0dc571)",
+      "L 2 (#5713f0)",
+      "D 2 (#d2c081)",
+      "R 2 (#59c680)",
+      "D 2 (#411b91)",
+      "L 5 (#8ceee2)",
+      "U 2 (#caa173)",
+      "L 1 (#1b58a2)",
+      "U 2 (#caa171)",
+      "R 2 (#7807d2)",
+      "U 3 (#a77fa3)",
+      "L 2 (#015232)",
+      "U 2 (#7a21e3)",
+    ];
+
+    let polygon = dig_polygon(&dig_plan, parse_dig_step);
+
+    assert_snapshot!("day_18_outline", trench(&polygon).render(|dug| if *dug { '#' } else { '.' }));
+    assert_eq!(polygon.lattice_points(), 62);
+    assert_eq!(calculate_hex_lava_volume(&dig_plan), 952408144115);
+  }
 }
@@ -1,35 +1,15 @@
 use std::collections::HashMap;
 
 use itertools::Itertools;
-use regex::Regex;
 
-pub fn accepted_part_rating_sum(input: &Vec<&str>) -> u32 {
-  let re_parts = Regex::new(r"([0-9]{1,})").unwrap();
-  let re_workflows = Regex::new(r"([a-zA-Z0-9<>:]{1,})").unwrap();
-  let re_workflow_parts = Regex::new(r"([a-zA-Z0-9]{1,})").unwrap();
-
-  let mut parts: Vec<Part> = Vec::new();
-  let mut workflows: HashMap<String, Vec<String>> = HashMap::new();
+use crate::utils::{
+  dot::{Graph, Shape},
+  interval::Interval,
+  parse,
+};
 
-  for line in input {
-    if line.len() == 0 {
-      continue;
-    } else if line.chars().nth(0).unwrap() == '{' {
-      let (x, m, a, s) = re_parts
-        .find_iter(line)
-        .map(|s| s.as_str().parse::<u32>().unwrap())
-        .collect_tuple()
-        .unwrap();
-      parts.push(Part { x, m, a, s });
-    } else {
-      let mut sliced_workflow = re_workflows
-        .find_iter(line)
-        .map(|m| m.as_str().to_owned())
-        .collect_vec();
-      let key = sliced_workflow.remove(0);
-      workflows.insert(key.to_owned(), sliced_workflow);
-    }
-  }
+pub fn accepted_part_rating_sum(input: &Vec<&str>) -> u32 {
+  let (workflows, parts) = parse_system(input);
 
   let mut accepted: Vec<Part> = Vec::new();
   for part in parts {
@@ -41,9 +21,7 @@ pub fn accepted_part_rating_sum(input: &Vec<&str>) -> u32 {
           accepted.push(part);
           break;
         }
-        _ => {
-          current = evaluate_workflow(&part, workflows.get(&current).unwrap(), &re_workflow_parts)
-        }
+        _ => current = evaluate_workflow(&part, workflows.get(&current).unwrap()),
       }
     }
   }
@@ -51,19 +29,120 @@ pub fn accepted_part_rating_sum(input: &Vec<&str>) -> u32 {
   accepted.iter().map(|p| p.x + p.m + p.a + p.s).sum()
 }
 
-fn evaluate_workflow(part: &Part, workflows: &Vec<String>, re: &Regex) -> String {
+/// Day 19, Part 2
+///
+/// Each of a part's four ratings can be anywhere from 1 to 4000. How
+/// many distinct combinations of ratings will the workflows accept?
+pub fn accepted_rating_combinations(input: &Vec<&str>) -> u64 {
+  let (workflows, _parts) = parse_system(input);
+
+  let all_ratings = [Interval::new(1, 4001); 4];
+  count_accepted(all_ratings, "in", &workflows)
+}
+
+/// Graph export for day 19: each workflow is a box with an edge per rule,
+/// labelled with the rule's condition. Parts enter at `in` and end up at
+/// A (accepted) or R (rejected).
+pub fn workflow_graph(input: &Vec<&str>) -> Graph {
+  let (workflows, _parts) = parse_system(input);
+
+  let mut graph = Graph::digraph("day_19");
+  graph.node("in").shape(Shape::House);
+  graph.node("A").shape(Shape::DoubleCircle).color("green");
+  graph.node("R").shape(Shape::Octagon).color("red");
+  for (name, rules) in workflows.iter().sorted() {
+    if name != "in" {
+      graph.node(name).shape(Shape::Box);
+    }
+    for rule in rules {
+      match rule.split_once(':') {
+        Some((condition, target)) => graph.edge(name, target).label(condition),
+        None => graph.edge(name, rule),
+      };
+    }
+  }
+  graph
+}
+
+fn parse_system(input: &Vec<&str>) -> (HashMap<String, Vec<String>>, Vec<Part>) {
+  let mut parts: Vec<Part> = Vec::new();
+  let mut workflows: HashMap<String, Vec<String>> = HashMap::new();
+
+  for line in input {
+    if line.len() == 0 {
+      continue;
+    } else if line.chars().nth(0).unwrap() == '{' {
+      let (x, m, a, s) = parse::unsigned_ints(line).into_iter().collect_tuple().unwrap();
+      parts.push(Part { x, m, a, s });
+    } else {
+      let (name, rules) = line
+        .strip_suffix('}')
+        .and_then(|workflow| workflow.split_once('{'))
+        .unwrap_or_else(|| panic!("Invalid workflow: '{}'", line));
+      workflows.insert(name.to_owned(), rules.split(',').map(|rule| rule.to_owned()).collect_vec());
+    }
+  }
+
+  (workflows, parts)
+}
+
+// Ratings are held as [x, m, a, s] ranges. Each rule splits off the slice
+// of ratings it matches and sends it on, leaving the rest for the next rule.
+fn count_accepted(
+  ratings: [Interval<u64>; 4],
+  workflow: &str,
+  workflows: &HashMap<String, Vec<String>>,
+) -> u64 {
+  if ratings.iter().any(|r| r.is_empty()) {
+    return 0;
+  }
+  match workflow {
+    "A" => return ratings.iter().map(|r| r.len()).product(),
+    "R" => return 0,
+    _ => {}
+  }
+
+  let mut remaining = ratings;
+  let mut total = 0;
+  for flow in workflows.get(workflow).unwrap() {
+    let Some((part_type, comparator, next_tag)) = parse_rule(flow) else {
+      return total + count_accepted(remaining, flow, workflows);
+    };
+    let index = match part_type {
+      "x" => 0,
+      "m" => 1,
+      "a" => 2,
+      "s" => 3,
+      _ => panic!("Invalid part type passed: {}", part_type),
+    };
+    let comparator_value = comparator.parse::<u64>().unwrap();
+
+    let (passed, failed) = if flow.contains(">") {
+      let (below, above) = remaining[index].split_at(comparator_value + 1);
+      (above, below)
+    } else {
+      remaining[index].split_at(comparator_value)
+    };
+
+    let mut sent = remaining;
+    sent[index] = passed;
+    total += count_accepted(sent, next_tag, workflows);
+    remaining[index] = failed;
+  }
+
+  total
+}
+
+fn evaluate_workflow(part: &Part, workflows: &Vec<String>) -> String {
   for flow in workflows {
     match flow.as_str() {
       "A" => return "A".to_owned(),
       "R" => return "R".to_owned(),
       _ => {
-        let matches = re.find_iter(&flow).map(|m| m.as_str()).collect_vec();
-        if matches.len() == 1 {
-          return matches[0].to_owned();
-        }
-
-        let (part_type, comparator, next_tag) = matches.iter().collect_tuple().unwrap();
-        let part_value = match *part_type {
+        let Some((part_type, comparator, next_tag)) = parse_rule(flow) else {
+          return flow.to_owned();
+        };
+        let part_value = match part_type {
           "x" => part.x,
           "m" => part.m,
           "a" => part.a,
@@ -90,6 +169,14 @@ fn evaluate_workflow(part: &Part, workflows: &Vec<String>, re: &Regex) -> String
   );
 }
 
+// Splits a rule like `a<2006:qkq` into its rating, value and target, or
+// gives `None` for a rule that's just a target
+fn parse_rule(flow: &str) -> Option<(&str, &str, &str)> {
+  parse::scan("{}<{}:{}", flow)
+    .or_else(|| parse::scan("{}>{}:{}", flow))
+    .map(|rule| (rule[0], rule[1], rule[2]))
+}
+
 #[derive(Debug, Clone, Copy)]
 struct Part {
   x: u32,
@@ -1,8 +1,10 @@
-use std::collections::{HashMap, VecDeque};
-use std::fmt::Debug;
+use std::collections::HashMap;
 
-use itertools::Itertools;
-use regex::Regex;
+use super::pulse_network::{ModuleType, Network, Pulse, Watch};
+use crate::utils::{
+  dot::{Graph, Shape},
+  math,
+};
 
 /// Day 20, Part 1 -- https://adventofcode.com/2023/day/20
 ///
@@ -45,208 +47,127 @@ use regex::Regex;
 /// Calculate how many low pulses and high pulses reach the output after
 /// pressing the button 1000 times. What is their product?
 pub fn find_frequency_product(input_modules: &Vec<&str>) -> u64 {
-  let mut modules: HashMap<String, Module> = HashMap::new();
-  let mut conjunctions = HashMap::new();
-  let re = Regex::new(r"([a-zA-Z]{1,})").unwrap();
-
-  // first time through, just collect the conjunction modules
-  for module in input_modules {
-    if module.chars().nth(0).unwrap() == '&' {
-      let (id, listeners) = get_input_parts(module, &re);
-      conjunctions.insert(id, Conjunction::new(&listeners));
-    }
-  }
-
-  // second time through, collect broadcast + flipflips and fill conjunction inputs
-  for module in input_modules {
-    let (id, listeners) = get_input_parts(module, &re);
-    match module.chars().nth(0).unwrap() {
-      '&' => {} // account for but skip
-      '%' => {
-        modules.insert(id.clone(), Module::FlipFlop(FlipFlop::new(&listeners)));
-        for listener in &listeners {
-          if let Some(conjunction) = conjunctions.get_mut(listener) {
-            conjunction.add_listener(id.clone());
-          }
-        }
-      }
-      'b' => {
-        modules.insert(
-          id.clone(),
-          Module::Broadcaster(Broadcaster::new(&listeners)),
-        );
-        for listener in &listeners {
-          if let Some(conjunction) = conjunctions.get_mut(listener) {
-            conjunction.add_listener(id.clone());
-          }
-        }
-      }
-
-      _ => panic!("Module type unaccounted for: {}", module),
-    }
-  }
-
-  for (id, conjunction) in conjunctions {
-    modules.insert(id, Module::Conjunction(conjunction));
-  }
-
+  let mut network = parse_network(input_modules);
   let mut low_pulses = 0;
   let mut high_pulses = 0;
-  for _i in 0..1000 {
-    if let Some((low, high)) = press_button(&mut modules) {
-      low_pulses += low;
-      high_pulses += high;
+  for _ in 0..1000 {
+    for signal in network.press() {
+      match signal.pulse {
+        Pulse::Low => low_pulses += 1,
+        Pulse::High => high_pulses += 1,
+      }
     }
   }
 
   low_pulses * high_pulses
 }
 
-fn press_button(modules: &mut HashMap<String, Module>) -> Option<(u64, u64)> {
-  let mut to_explore: VecDeque<(Pulse, String, String)> = VecDeque::new();
-  to_explore.push_back((Pulse::LOW, "broadcaster".to_owned(), "button".to_owned()));
-  let mut low_outputs = 0_u64;
-  let mut high_outputs = 0_u64;
-  while to_explore.len() > 0 {
-    let (pulse, target, sender) = to_explore.pop_front().unwrap();
-    match pulse {
-      Pulse::LOW => low_outputs += 1,
-      Pulse::HIGH => high_outputs += 1,
-    }
-
-    let module = modules.get_mut(&target);
-    if module.is_none() {
-      continue;
-    }
-
-    if let Some((pulse, targets)) = module.unwrap().process_pulse(pulse, sender) {
-      for t in targets {
-        if t == "output" {
-          match pulse {
-            Pulse::LOW => low_outputs += 1,
-            Pulse::HIGH => high_outputs += 1,
-          }
-
-          if t == "rx" && pulse == Pulse::LOW {
-            return None;
-          }
-        } else {
-          to_explore.push_back((pulse, t, target.clone()));
-        }
+/// Day 20, Part 2
+///
+/// The final machine is connected to a module named rx. It turns on once
+/// rx receives a single low pulse. What is the fewest number of button
+/// presses needed to deliver a low pulse to rx?
+///
+/// Simulating until that happens would take far too long. rx is fed by a
+/// single conjunction, which only sends low once every one of its inputs
+/// has last sent it a high pulse. Each of those inputs sends high on a
+/// fixed cycle, so the answer is the LCM of their cycle lengths.
+pub fn find_presses_to_rx(input_modules: &Vec<&str>) -> u64 {
+  let mut network = parse_network(input_modules);
+  let rx = network.id("rx").expect("No rx module in the network");
+
+  let feeder = match network.inputs(rx) {
+    [feeder] if network.module_type(*feeder) == ModuleType::Conjunction => *feeder,
+    // Not the usual shape, so just press until it happens
+    _ => {
+      let mut watch = Watch::new(rx, Pulse::Low);
+      while watch.fired.is_empty() {
+        network.press_observed(&mut [&mut watch]);
       }
+      return network.presses();
     }
-  }
-
-  Some((low_outputs, high_outputs))
-}
-
-#[derive(Debug, Clone)]
-enum Module {
-  FlipFlop(FlipFlop),
-  Conjunction(Conjunction),
-  Broadcaster(Broadcaster),
-}
-
-impl Module {
-  pub fn process_pulse(&mut self, pulse: Pulse, sender: String) -> Option<(Pulse, Vec<String>)> {
-    // println!("Module State: {:?}", self);
-    match self {
-      Module::FlipFlop(module) => module.process_pulse(pulse, sender),
-      Module::Conjunction(module) => module.process_pulse(pulse, sender),
-      Module::Broadcaster(module) => module.process_pulse(pulse, sender),
-    }
-  }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Pulse {
-  HIGH,
-  LOW,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct FlipFlop {
-  on: bool,
-  listeners: Vec<String>,
-}
-
-impl FlipFlop {
-  pub fn new(listener_ids: &Vec<String>) -> FlipFlop {
-    FlipFlop {
-      on: false,
-      listeners: listener_ids.clone(),
-    }
-  }
-
-  fn process_pulse(&mut self, pulse: Pulse, _sender: String) -> Option<(Pulse, Vec<String>)> {
-    match pulse {
-      Pulse::HIGH => return None,
-      Pulse::LOW => {
-        self.on = !self.on;
-        if self.on {
-          return Some((Pulse::HIGH, self.listeners.clone()));
-        } else {
-          return Some((Pulse::LOW, self.listeners.clone()));
+  };
+
+  // Wait for every input to send high twice, to check it repeats evenly
+  let inputs = network.inputs(feeder).to_vec();
+  let mut watch = Watch::new(feeder, Pulse::High);
+  let mut firsts = HashMap::new();
+  let mut periods = HashMap::new();
+  while periods.len() < inputs.len() {
+    watch.fired.clear();
+    network.press_observed(&mut [&mut watch]);
+    for &(press, input) in &watch.fired {
+      match firsts.get(&input) {
+        None => {
+          firsts.insert(input, press);
+        }
+        Some(&first) if first != press && !periods.contains_key(&input) => {
+          assert_eq!(press, 2 * first, "{} doesn't repeat every {} presses", network.name(input), first);
+          periods.insert(input, first);
         }
+        Some(_) => {}
       }
     }
   }
-}
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Conjunction {
-  inputs: HashMap<String, Pulse>,
-  listeners: Vec<String>,
+  math::lcm_of(periods.into_values())
 }
 
-impl Conjunction {
-  pub fn new(listener_ids: &Vec<String>) -> Conjunction {
-    return Conjunction {
-      inputs: HashMap::new(),
-      listeners: listener_ids.clone(),
+/// Graph export for day 20, with a shape per module type: flip-flops are
+/// boxes, conjunctions diamonds and the broadcaster a house fed by the
+/// button. Modules with no type of their own (like `rx`) are double
+/// circles.
+pub fn module_graph(input_modules: &Vec<&str>) -> Graph {
+  let network = parse_network(input_modules);
+  let mut graph = Graph::digraph("day_20");
+  for id in network.ids() {
+    let name = network.name(id);
+    match network.module_type(id) {
+      ModuleType::Button => graph.node(name).shape(Shape::InvHouse),
+      ModuleType::Broadcaster => graph.node(name).shape(Shape::House).label(name),
+      ModuleType::FlipFlop => graph.node(name).shape(Shape::Box).label(format!("%{}", name)),
+      ModuleType::Conjunction => graph.node(name).shape(Shape::Diamond).label(format!("&{}", name)),
+      ModuleType::Sink => graph.node(name).shape(Shape::DoubleCircle),
     };
-  }
-
-  pub fn add_listener(&mut self, sender_id: String) {
-    self.inputs.insert(sender_id, Pulse::LOW);
-  }
-
-  fn process_pulse(&mut self, pulse: Pulse, sender: String) -> Option<(Pulse, Vec<String>)> {
-    self.inputs.insert(sender, pulse);
-    match self
-      .inputs
-      .iter()
-      .all(|(_name, pulse)| pulse == &Pulse::HIGH)
-    {
-      true => Some((Pulse::LOW, self.listeners.clone())),
-      false => Some((Pulse::HIGH, self.listeners.clone())),
+    for &output in network.outputs(id) {
+      graph.edge(name, network.name(output));
     }
   }
+  graph
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Broadcaster {
-  listeners: Vec<String>,
+// Helpers
+fn parse_network(input_modules: &[&str]) -> Network {
+  Network::parse(input_modules).unwrap_or_else(|e| panic!("Invalid module configuration, {}", e))
 }
 
-impl Broadcaster {
-  pub fn new(listener_ids: &Vec<String>) -> Broadcaster {
-    return Broadcaster {
-      listeners: listener_ids.clone(),
-    };
-  }
-
-  fn process_pulse(&mut self, pulse: Pulse, _sender: String) -> Option<(Pulse, Vec<String>)> {
-    return Some((pulse, self.listeners.clone()));
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn presses_to_rx_matches_brute_force() {
+    // Two counters that reset after 3 and 5 presses, each pulsing high
+    // into the conjunction feeding rx as they wrap
+    let modules = vec![
+      "broadcaster -> a0, b0",
+      "%a0 -> a1, ka",
+      "%a1 -> ka",
+      "&ka -> a0, ia",
+      "&ia -> hub",
+      "%b0 -> b1, kb",
+      "%b1 -> b2",
+      "%b2 -> kb",
+      "&kb -> b0, b1, ib",
+      "&ib -> hub",
+      "&hub -> rx",
+    ];
+    let mut network = parse_network(&modules);
+    let mut watch = Watch::new(network.id("rx").unwrap(), Pulse::Low);
+    while watch.fired.is_empty() {
+      network.press_observed(&mut [&mut watch]);
+    }
+    assert_eq!(network.presses(), 15);
+    assert_eq!(find_presses_to_rx(&modules), 15);
   }
 }
-
-// Helpers
-fn get_input_parts(input: &str, re: &Regex) -> (String, Vec<String>) {
-  let parts = re
-    .find_iter(input)
-    .map(|m| m.as_str().to_owned())
-    .collect_vec();
-
-  (parts[0].clone(), parts[1..].to_vec())
-}
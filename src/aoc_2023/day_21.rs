@@ -1,11 +1,10 @@
-use itertools::Itertools;
-use std::collections::HashSet;
-
-type Vector2D = (isize, isize);
-const NORTH: Vector2D = (-1, 0);
-const SOUTH: Vector2D = (1, 0);
-const EAST: Vector2D = (0, 1);
-const WEST: Vector2D = (0, -1);
+use crate::utils::{
+  bitgrid::BitGrid,
+  geometry::Vec2,
+  grid::Grid,
+  image::{self, Image, Rgb},
+  polynomial::Polynomial,
+};
 
 /// Day 21, Part 1 -- https://adventofcode.com/2023/day/21
 ///
@@ -20,55 +19,104 @@ const WEST: Vector2D = (0, -1);
 /// How many locations can he access if he can only step on Garden Plots
 /// and given that he needs to walk exactly 64 steps?
 pub fn find_accessible_gardening_plots(garden_str: &Vec<&str>) -> usize {
-  let mut garden = garden_str
-    .iter()
-    .map(|row| row.chars().collect_vec())
-    .collect_vec();
+  let mut garden = Grid::parse(garden_str);
 
-  let mut positions: HashSet<Vector2D> = HashSet::new();
-  let start_pos = get_start_pos(&garden);
-  garden[start_pos.0 as usize][start_pos.1 as usize] = '.';
-  positions.insert(start_pos);
+  let start_pos = Vec2::from(garden.find(&'S').expect("No start position found in map."));
+  garden[start_pos] = '.';
+  let plots = BitGrid::from_grid(&garden, |&c| c == '.');
+  let mut positions = BitGrid::new(garden.width(), garden.height());
+  positions.set(start_pos, true);
 
   for _ in 0..64 {
-    positions = step(&positions, &garden);
+    positions = step(&positions, &plots);
   }
+  image::debug_dump("2023_day_21_plots", || {
+    let mut colors = garden.map(|&c| if c == '#' { Rgb::GREY } else { Rgb::DARK });
+    positions.ones().for_each(|pos| colors[pos] = Rgb::GREEN);
+    Image::from_grid(&colors, 4, |rgb| *rgb)
+  });
 
-  return positions.len();
+  return positions.count_ones();
 }
 
-fn get_start_pos(garden: &Vec<Vec<char>>) -> Vector2D {
-  for i in 0..garden.len() {
-    for j in 0..garden[i].len() {
-      if 'S' == garden[i][j] {
-        return (i as isize, j as isize);
-      }
-    }
-  }
-  panic!("No start position found in map.");
-}
+/// Day 21, Part 2
+///
+/// The elf's garden actually repeats infinitely in every direction, and
+/// he needs to know how many plots he can reach in exactly 26501365
+/// steps.
+///
+/// The start sits in the middle of a clear row and column, so the
+/// reachable area grows as a diamond and the count is a quadratic in the
+/// number of whole garden widths walked. Three samples fit it exactly.
+pub fn find_accessible_infinite_gardening_plots(garden_str: &Vec<&str>) -> i128 {
+  const STEPS: usize = 26501365;
 
-fn step(positions: &HashSet<Vector2D>, garden: &Vec<Vec<char>>) -> HashSet<Vector2D> {
-  let mut new_positions: HashSet<Vector2D> = HashSet::new();
-  for pos in positions {
-    vec![NORTH, SOUTH, EAST, WEST].iter().for_each(|dir| {
-      let new_pos = v_add(*dir, *pos);
-      if pos_valid(new_pos, garden) {
-        new_positions.insert(new_pos);
-      }
-    })
-  }
-  new_positions
+  let mut garden = Grid::parse(garden_str);
+  let start_pos = Vec2::from(garden.find(&'S').expect("No start position found in map."));
+  garden[start_pos] = '.';
+
+  let size = garden.height();
+  let offset = STEPS % size;
+  let counts = reachable_counts(&garden, start_pos, offset + 2 * size);
+  let samples = (0..3)
+    .map(|i| (i as i128, counts[offset + i * size] as i128))
+    .collect::<Vec<_>>();
+
+  Polynomial::fit(&samples).eval_integer((STEPS / size) as i128)
 }
 
-fn pos_valid(pos: Vector2D, garden: &Vec<Vec<char>>) -> bool {
-  if pos.0 < 0 || pos.1 < 0 || pos.0 >= garden.len() as isize || pos.1 >= garden[0].len() as isize {
-    return false;
+// Helpers
+
+// Number of plots reachable after each of 0..=max_steps steps, with the
+// garden tiled out far enough in every direction that the walk can't
+// reach the edge
+fn reachable_counts(garden: &Grid<char>, start_pos: Vec2, max_steps: usize) -> Vec<usize> {
+  let (width, height) = (garden.width(), garden.height());
+  let copies = max_steps.div_ceil(width.min(height));
+  let tiles = 2 * copies + 1;
+
+  let top_left = Vec2::new(-((copies * width) as isize), -((copies * height) as isize));
+  let plots = BitGrid::from_grid(&garden.tiled().window(top_left, width * tiles, height * tiles), |&c| c == '.');
+
+  let mut positions = BitGrid::new(plots.width(), plots.height());
+  positions.set(start_pos - top_left, true);
+  let mut counts = vec![positions.count_ones()];
+  for _ in 0..max_steps {
+    positions = step(&positions, &plots);
+    counts.push(positions.count_ones());
   }
+  counts
+}
 
-  garden[pos.0 as usize][pos.1 as usize] == '.'
+fn step(positions: &BitGrid, plots: &BitGrid) -> BitGrid {
+  let mut new_positions = positions.neighbours4();
+  new_positions &= plots;
+  new_positions
 }
 
-fn v_add(v1: Vector2D, v2: Vector2D) -> Vector2D {
-  return (v1.0 + v2.0, v1.1 + v2.1);
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn example_infinite_garden() {
+    let garden = Grid::parse(&[
+      "...........",
+      ".....###.#.",
+      ".###.##..#.",
+      "..#.#...#..",
+      "....#.#....",
+      ".##...####.",
+      ".##..#...#.",
+      ".......##..",
+      ".##.#.####.",
+      ".##..##.##.",
+      "...........",
+    ]);
+    let counts = reachable_counts(&garden, Vec2::new(5, 5), 100);
+    assert_eq!(counts[6], 16);
+    assert_eq!(counts[10], 50);
+    assert_eq!(counts[50], 1594);
+    assert_eq!(counts[100], 6536);
+  }
 }
@@ -0,0 +1,349 @@
+#![allow(dead_code)]
+
+use std::{
+  collections::{hash_map::DefaultHasher, HashMap, VecDeque},
+  hash::{Hash, Hasher},
+};
+
+use crate::utils::parse::ParseError;
+
+/// Index of a module in its [`Network`].
+pub type ModuleId = usize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pulse {
+  High,
+  Low,
+}
+
+/// One pulse travelling down a cable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Signal {
+  pub from: ModuleId,
+  pub to: ModuleId,
+  pub pulse: Pulse,
+}
+
+/// What a module does with the pulses it gets, leaving out its state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModuleType {
+  Button,
+  Broadcaster,
+  FlipFlop,
+  Conjunction,
+  Sink,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Kind {
+  Button,
+  Broadcaster,
+  FlipFlop { on: bool },
+  /// The last pulse from each input, in the same order as `inputs`.
+  Conjunction { memory: Vec<Pulse> },
+  /// Named as a destination but never defined, like `output` or `rx`.
+  Sink,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Module {
+  name: String,
+  kind: Kind,
+  inputs: Vec<ModuleId>,
+  outputs: Vec<ModuleId>,
+}
+
+/// Something that wants to see every pulse as it's delivered. Any
+/// `FnMut(u64, &Signal)` closure (press number, pulse) is an observer.
+pub trait Observer {
+  fn pulse(&mut self, press: u64, signal: &Signal);
+}
+
+impl<F: FnMut(u64, &Signal)> Observer for F {
+  fn pulse(&mut self, press: u64, signal: &Signal) {
+    self(press, signal)
+  }
+}
+
+/// Fires when a given module receives a given pulse, remembering the
+/// press it happened on and who sent it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Watch {
+  pub module: ModuleId,
+  pub pulse: Pulse,
+  pub fired: Vec<(u64, ModuleId)>,
+}
+
+impl Watch {
+  pub fn new(module: ModuleId, pulse: Pulse) -> Watch {
+    Watch {
+      module,
+      pulse,
+      fired: Vec::new(),
+    }
+  }
+}
+
+impl Observer for Watch {
+  fn pulse(&mut self, press: u64, signal: &Signal) {
+    if signal.to == self.module && signal.pulse == self.pulse {
+      self.fired.push((press, signal.from));
+    }
+  }
+}
+
+/// Every flip-flop and conjunction memory, for spotting repeated states.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Snapshot(Vec<Kind>);
+
+/// The wired-up modules from day 20. The button is a module of its own,
+/// wired to the broadcaster, so every pulse has a sender.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Network {
+  modules: Vec<Module>,
+  ids: HashMap<String, ModuleId>,
+  button: ModuleId,
+  broadcaster: ModuleId,
+  presses: u64,
+}
+
+impl Network {
+  /// Parses lines like `%a -> inv, con`, skipping blank ones.
+  pub fn parse(lines: &[&str]) -> Result<Network, ParseError> {
+    let mut network = Network {
+      modules: Vec::new(),
+      ids: HashMap::new(),
+      button: 0,
+      broadcaster: 0,
+      presses: 0,
+    };
+    network.button = network.id_or_insert("button");
+    network.modules[network.button].kind = Kind::Button;
+    network.broadcaster = network.id_or_insert("broadcaster");
+
+    let mut found_broadcaster = false;
+    for (i, line) in lines.iter().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+      let (module, listeners) = line
+        .split_once(" -> ")
+        .ok_or_else(|| ParseError::new(i + 1, format!("expected '<module> -> <listeners>', got '{}'", line)))?;
+      let (kind, name) = match module.trim() {
+        "broadcaster" => (Kind::Broadcaster, "broadcaster"),
+        module if module.starts_with('%') => (Kind::FlipFlop { on: false }, &module[1..]),
+        module if module.starts_with('&') => (Kind::Conjunction { memory: Vec::new() }, &module[1..]),
+        module => return Err(ParseError::new(i + 1, format!("unknown module type '{}'", module))),
+      };
+      found_broadcaster |= kind == Kind::Broadcaster;
+
+      let id = network.id_or_insert(name);
+      network.modules[id].kind = kind;
+      for listener in listeners.split(',').map(str::trim) {
+        let listener = network.id_or_insert(listener);
+        network.connect(id, listener);
+      }
+    }
+    if !found_broadcaster {
+      return Err(ParseError::new(lines.len(), "no broadcaster module"));
+    }
+    network.connect(network.button, network.broadcaster);
+
+    // Conjunctions start out remembering a low pulse from every input
+    for module in &mut network.modules {
+      if let Kind::Conjunction { memory } = &mut module.kind {
+        *memory = vec![Pulse::Low; module.inputs.len()];
+      }
+    }
+    Ok(network)
+  }
+
+  pub fn id(&self, name: &str) -> Option<ModuleId> {
+    self.ids.get(name).copied()
+  }
+
+  pub fn name(&self, id: ModuleId) -> &str {
+    &self.modules[id].name
+  }
+
+  /// The modules wired into `id`.
+  pub fn inputs(&self, id: ModuleId) -> &[ModuleId] {
+    &self.modules[id].inputs
+  }
+
+  /// The modules `id` sends its pulses to.
+  pub fn outputs(&self, id: ModuleId) -> &[ModuleId] {
+    &self.modules[id].outputs
+  }
+
+  pub fn module_type(&self, id: ModuleId) -> ModuleType {
+    match self.modules[id].kind {
+      Kind::Button => ModuleType::Button,
+      Kind::Broadcaster => ModuleType::Broadcaster,
+      Kind::FlipFlop { .. } => ModuleType::FlipFlop,
+      Kind::Conjunction { .. } => ModuleType::Conjunction,
+      Kind::Sink => ModuleType::Sink,
+    }
+  }
+
+  /// Every module, the button first, then in the order they were named.
+  pub fn ids(&self) -> impl Iterator<Item = ModuleId> {
+    0..self.modules.len()
+  }
+
+  /// Button presses so far.
+  pub fn presses(&self) -> u64 {
+    self.presses
+  }
+
+  /// Presses the button once and returns every pulse sent, in the order
+  /// they were delivered.
+  pub fn press(&mut self) -> Vec<Signal> {
+    let mut log = Vec::new();
+    self.press_observed(&mut [&mut |_, signal: &Signal| log.push(*signal)]);
+    log
+  }
+
+  /// Presses the button once, showing each pulse to every observer as
+  /// it's delivered.
+  pub fn press_observed(&mut self, observers: &mut [&mut dyn Observer]) {
+    self.presses += 1;
+    let mut queue = VecDeque::from([Signal {
+      from: self.button,
+      to: self.broadcaster,
+      pulse: Pulse::Low,
+    }]);
+    while let Some(signal) = queue.pop_front() {
+      for observer in observers.iter_mut() {
+        observer.pulse(self.presses, &signal);
+      }
+      if let Some(pulse) = self.deliver(&signal) {
+        let from = signal.to;
+        queue.extend(self.modules[from].outputs.iter().map(|&to| Signal { from, to, pulse }));
+      }
+    }
+  }
+
+  /// Copies out every module's state.
+  pub fn snapshot(&self) -> Snapshot {
+    Snapshot(self.modules.iter().map(|module| module.kind.clone()).collect())
+  }
+
+  /// Puts every module back how it was in `snapshot`. The press count is
+  /// left alone.
+  pub fn restore(&mut self, snapshot: &Snapshot) {
+    for (module, kind) in self.modules.iter_mut().zip(&snapshot.0) {
+      module.kind = kind.clone();
+    }
+  }
+
+  /// A hash of every module's state, for cheap cycle checks.
+  pub fn state_hash(&self) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    self.snapshot().hash(&mut hasher);
+    hasher.finish()
+  }
+
+  // Helpers
+  fn id_or_insert(&mut self, name: &str) -> ModuleId {
+    if let Some(id) = self.id(name) {
+      return id;
+    }
+    self.modules.push(Module {
+      name: name.to_string(),
+      kind: Kind::Sink,
+      inputs: Vec::new(),
+      outputs: Vec::new(),
+    });
+    self.ids.insert(name.to_string(), self.modules.len() - 1);
+    self.modules.len() - 1
+  }
+
+  fn connect(&mut self, from: ModuleId, to: ModuleId) {
+    self.modules[from].outputs.push(to);
+    self.modules[to].inputs.push(from);
+  }
+
+  // Updates the receiving module and returns the pulse it sends on, if any
+  fn deliver(&mut self, signal: &Signal) -> Option<Pulse> {
+    let module = &mut self.modules[signal.to];
+    match &mut module.kind {
+      Kind::Button | Kind::Sink => None,
+      Kind::Broadcaster => Some(signal.pulse),
+      Kind::FlipFlop { on } => match signal.pulse {
+        Pulse::High => None,
+        Pulse::Low => {
+          *on = !*on;
+          Some(if *on { Pulse::High } else { Pulse::Low })
+        }
+      },
+      Kind::Conjunction { memory } => {
+        let input = module.inputs.iter().position(|&id| id == signal.from).unwrap();
+        memory[input] = signal.pulse;
+        match memory.iter().all(|&pulse| pulse == Pulse::High) {
+          true => Some(Pulse::Low),
+          false => Some(Pulse::High),
+        }
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn counter() -> Network {
+    Network::parse(&["broadcaster -> a", "%a -> inv, con", "&inv -> b", "%b -> con", "&con -> output"]).unwrap()
+  }
+
+  #[test]
+  fn logs_each_press() {
+    let mut network = counter();
+    let log = network.press();
+    let described: Vec<String> = log
+      .iter()
+      .map(|s| format!("{} -{:?}-> {}", network.name(s.from), s.pulse, network.name(s.to)))
+      .collect();
+    assert_eq!(
+      described,
+      vec![
+        "button -Low-> broadcaster",
+        "broadcaster -Low-> a",
+        "a -High-> inv",
+        "a -High-> con",
+        "inv -Low-> b",
+        "con -High-> output",
+        "b -High-> con",
+        "con -Low-> output",
+      ]
+    );
+    assert_eq!(network.presses(), 1);
+  }
+
+  #[test]
+  fn watches_and_snapshots() {
+    let mut network = counter();
+    let initial = network.snapshot();
+    let initial_hash = network.state_hash();
+
+    let mut watch = Watch::new(network.id("output").unwrap(), Pulse::Low);
+    for _ in 0..4 {
+      network.press_observed(&mut [&mut watch]);
+    }
+    // Back where it started after four presses
+    assert_eq!(network.snapshot(), initial);
+    assert_eq!(network.state_hash(), initial_hash);
+    assert_eq!(watch.fired.iter().map(|(press, _)| *press).collect::<Vec<_>>(), vec![1, 3]);
+
+    network.press();
+    assert_ne!(network.snapshot(), initial);
+    network.restore(&initial);
+    assert_eq!(network.snapshot(), initial);
+  }
+
+  #[test]
+  fn rejects_unknown_modules() {
+    assert_eq!(
+      Network::parse(&["broadcaster -> a", "*a -> b"]),
+      Err(ParseError::new(2, "unknown module type '*a'"))
+    );
+  }
+}
@@ -0,0 +1,212 @@
+use std::{
+  env, fmt, fs,
+  path::{Path, PathBuf},
+  time::Duration,
+};
+
+use serde::Deserialize;
+
+const CONFIG_FILE_NAME: &str = "aoc.toml";
+
+/// How answers get printed once a day has run. `Json` only keeps stdout
+/// parseable for days that don't `println!` while solving, since solver
+/// output isn't captured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+  Text,
+  Json,
+}
+
+impl OutputFormat {
+  fn parse(value: &str) -> Result<OutputFormat, String> {
+    match value {
+      "text" => Ok(OutputFormat::Text),
+      "json" => Ok(OutputFormat::Json),
+      _ => Err(format!("Unknown output format '{}', expected text or json", value)),
+    }
+  }
+}
+
+impl fmt::Display for OutputFormat {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      OutputFormat::Text => write!(f, "text"),
+      OutputFormat::Json => write!(f, "json"),
+    }
+  }
+}
+
+/// Raw contents of an `aoc.toml`, every key optional.
+///
+/// ```toml
+/// year = 2023
+/// input_root = "./src/inputs"
+/// output_format = "text"
+/// timeout_secs = 30
+/// threads = 4
+/// session_token_path = "~/.config/aoc/session"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+  year: Option<u32>,
+  input_root: Option<PathBuf>,
+  output_format: Option<OutputFormat>,
+  timeout_secs: Option<u64>,
+  threads: Option<usize>,
+  session_token_path: Option<PathBuf>,
+}
+
+/// The effective settings for a run: built-in defaults, then whatever
+/// `aoc.toml` sets, then any CLI flags on top.
+#[derive(Debug, Clone)]
+pub struct Config {
+  pub year: Option<u32>,
+  pub input_root: PathBuf,
+  pub output_format: OutputFormat,
+  pub timeout: Option<Duration>,
+  pub threads: usize,
+  pub session_token_path: Option<PathBuf>,
+  pub source: Option<PathBuf>,
+}
+
+impl Default for Config {
+  fn default() -> Config {
+    Config {
+      year: None,
+      input_root: PathBuf::from("./src/inputs"),
+      output_format: OutputFormat::Text,
+      timeout: None,
+      threads: 1,
+      session_token_path: None,
+      source: None,
+    }
+  }
+}
+
+impl Config {
+  /// Loads the first `aoc.toml` found at the repo root (the working
+  /// directory) or in the user config dir, falling back to defaults
+  /// when neither exists.
+  pub fn load() -> Result<Config, String> {
+    match Config::candidate_paths().into_iter().find(|p| p.is_file()) {
+      Some(path) => Config::from_file(&path),
+      None => Ok(Config::default()),
+    }
+  }
+
+  pub fn from_file(path: &Path) -> Result<Config, String> {
+    let contents =
+      fs::read_to_string(path).map_err(|e| format!("Unable to read {:?}: {}", path, e))?;
+    let file: ConfigFile =
+      toml::from_str(&contents).map_err(|e| format!("Invalid config {:?}: {}", path, e))?;
+
+    let defaults = Config::default();
+    Ok(Config {
+      year: file.year,
+      input_root: file.input_root.map(expand_home).unwrap_or(defaults.input_root),
+      output_format: file.output_format.unwrap_or(defaults.output_format),
+      timeout: file.timeout_secs.map(Duration::from_secs),
+      threads: file.threads.unwrap_or(defaults.threads).max(1),
+      session_token_path: file.session_token_path.map(expand_home),
+      source: Some(path.to_path_buf()),
+    })
+  }
+
+  /// Applies a single `--flag value` pair from the command line. Returns
+  /// `Ok(false)` when the flag isn't a config flag so the caller can
+  /// handle it.
+  pub fn apply_flag(&mut self, flag: &str, value: &str) -> Result<bool, String> {
+    let invalid = |e: &dyn fmt::Display| format!("Invalid value '{}' for {}: {}", value, flag, e);
+    match flag {
+      "--year" => self.year = Some(value.parse().map_err(|e| invalid(&e))?),
+      "--input-root" => self.input_root = expand_home(PathBuf::from(value)),
+      "--format" => self.output_format = OutputFormat::parse(value)?,
+      "--timeout" => self.timeout = Some(Duration::from_secs(value.parse().map_err(|e| invalid(&e))?)),
+      "--threads" => self.threads = value.parse::<usize>().map_err(|e| invalid(&e))?.max(1),
+      "--session-token" => self.session_token_path = Some(expand_home(PathBuf::from(value))),
+      _ => return Ok(false),
+    }
+    Ok(true)
+  }
+
+  /// Path of the puzzle input for a given year/day under the input root.
+  pub fn input_path(&self, year: u32, day: u32) -> PathBuf {
+    self.input_root.join(year.to_string()).join(format!("day_{:02}.txt", day))
+  }
+
+  /// Prints the effective settings for `config show`.
+  pub fn show(&self) {
+    let source = match &self.source {
+      Some(path) => format!("{}", path.display()),
+      None => "none (defaults)".to_owned(),
+    };
+    let year = self.year.map_or("unset".to_owned(), |y| y.to_string());
+    let timeout = self.timeout.map_or("none".to_owned(), |t| format!("{}s", t.as_secs()));
+    let session = match &self.session_token_path {
+      Some(path) if path.is_file() => format!("{}", path.display()),
+      Some(path) => format!("{} (missing)", path.display()),
+      None => "unset".to_owned(),
+    };
+
+    println!("Config file:        {}", source);
+    println!("  year:             {}", year);
+    println!("  input_root:       {}", self.input_root.display());
+    println!("  output_format:    {}", self.output_format);
+    println!("  timeout:          {}", timeout);
+    println!("  threads:          {}", self.threads);
+    println!("  session_token:    {}", session);
+  }
+
+  fn candidate_paths() -> Vec<PathBuf> {
+    let mut paths = vec![PathBuf::from(CONFIG_FILE_NAME)];
+    if let Some(dir) = env::var_os("XDG_CONFIG_HOME") {
+      paths.push(PathBuf::from(dir).join("aoc").join(CONFIG_FILE_NAME));
+    }
+    if let Some(home) = env::var_os("HOME") {
+      paths.push(PathBuf::from(home).join(".config").join("aoc").join(CONFIG_FILE_NAME));
+    }
+    paths
+  }
+}
+
+// Helpers
+fn expand_home(path: PathBuf) -> PathBuf {
+  match (path.strip_prefix("~"), env::var_os("HOME")) {
+    (Ok(rest), Some(home)) => PathBuf::from(home).join(rest),
+    _ => path,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn flags_override_file_values() {
+    let mut config = Config {
+      year: Some(2022),
+      ..Config::default()
+    };
+
+    assert_eq!(config.apply_flag("--year", "2023"), Ok(true));
+    assert_eq!(config.apply_flag("--format", "json"), Ok(true));
+    assert_eq!(config.apply_flag("--threads", "0"), Ok(true));
+    assert_eq!(config.apply_flag("--inputs", "dir"), Ok(false));
+
+    assert_eq!(config.year, Some(2023));
+    assert_eq!(config.output_format, OutputFormat::Json);
+    assert_eq!(config.threads, 1);
+    assert!(config.apply_flag("--timeout", "soon").is_err());
+  }
+
+  #[test]
+  fn parses_config_file() {
+    let file: ConfigFile = toml::from_str("year = 2020\noutput_format = \"json\"\ntimeout_secs = 5").unwrap();
+    assert_eq!(file.year, Some(2020));
+    assert_eq!(file.output_format, Some(OutputFormat::Json));
+    assert_eq!(file.timeout_secs, Some(5));
+    assert!(toml::from_str::<ConfigFile>("yeer = 2020").is_err());
+  }
+}
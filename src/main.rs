@@ -1,4 +1,4 @@
-use std::{env, fs};
+use std::{env, ops::RangeInclusive, path::Path, process, time::SystemTime};
 
 mod aoc_2018;
 mod aoc_2019;
@@ -6,32 +6,145 @@ mod aoc_2020;
 mod aoc_2021;
 mod aoc_2022;
 mod aoc_2023;
+mod config;
+mod runner;
+mod utils;
+
+use config::{Config, OutputFormat};
+
+const USAGE: &str = "Usage:
+  run [[<year>] <day>] [--inputs <dir>]
+  graph <year> <day> [--out <file>]
+  visualize <year> <day> [--fps <n>] [--dump <file>]
+  trace <year> <day> [--cycles <first>-<last>]
+  config show
+
+Options (override aoc.toml):
+  --year <year>  --input-root <dir>  --format <text|json>
+  --timeout <secs>  --threads <n>  --session-token <path>
+
+Debugging:
+  --debug-images <dir>  save PNGs from days that draw their maps";
 
 fn main() {
   let args: Vec<String> = env::args().collect();
-  println!("Args: {:?}", args);
+  let mut config = Config::load().unwrap_or_else(|e| exit_with(&e));
+
+  // `run` is optional so the original `<year> <day>` invocation keeps working
+  let (command, rest) = match args.get(1).map(|s| s.as_str()) {
+    Some("run") => ("run", &args[2..]),
+    Some("config") => ("config", &args[2..]),
+    Some("graph") => ("graph", &args[2..]),
+    Some("visualize") => ("visualize", &args[2..]),
+    Some("trace") => ("trace", &args[2..]),
+    Some(_) => ("run", &args[1..]),
+    None => exit_with(USAGE),
+  };
+
+  let mut positional: Vec<&str> = Vec::new();
+  let mut inputs_dir: Option<&str> = None;
+  let mut graph_out: Option<&str> = None;
+  let mut fps: f64 = 10.0;
+  let mut frame_dump: Option<&str> = None;
+  let mut trace_cycles: Option<RangeInclusive<u64>> = None;
+  let mut arg_iter = rest.iter();
+  while let Some(arg) = arg_iter.next() {
+    if !arg.starts_with("--") {
+      positional.push(arg);
+      continue;
+    }
+
+    let value = arg_iter
+      .next()
+      .unwrap_or_else(|| exit_with(&format!("Missing value for {}\n\n{}", arg, USAGE)));
+    match config.apply_flag(arg, value) {
+      Ok(true) => {}
+      Ok(false) => match (command, arg.as_str()) {
+        ("run", "--inputs") => inputs_dir = Some(value),
+        ("graph", "--out") => graph_out = Some(value),
+        ("visualize", "--fps") => {
+          fps = value
+            .parse()
+            .ok()
+            .filter(|fps: &f64| *fps > 0.0)
+            .unwrap_or_else(|| exit_with(&format!("Invalid --fps '{}', expected a positive number", value)))
+        }
+        ("visualize", "--dump") => frame_dump = Some(value),
+        ("trace", "--cycles") => trace_cycles = Some(parse_cycles(value)),
+        (_, "--debug-images") => utils::image::enable_debug_output(Path::new(value))
+          .unwrap_or_else(|e| exit_with(&format!("Unable to create debug image dir {}: {}", value, e))),
+        (_, "--inputs" | "--out" | "--fps" | "--dump" | "--cycles") => {
+          exit_with(&format!("{} can't be used with {}\n\n{}", arg, command, USAGE))
+        }
+        _ => exit_with(&format!("Unknown flag {}\n\n{}", arg, USAGE)),
+      },
+      Err(e) => exit_with(&e),
+    }
+  }
 
-  if args.len() > 3 {
-    panic!("Invalid args found, day number must passed")
+  if config.output_format == OutputFormat::Text {
+    println!("Args: {:?}", args);
   }
 
-  let year_number = args[1].parse::<u32>().unwrap();
-  let day_number = args[2].parse::<u32>().unwrap();
-  let input_path = format!("./src/inputs/{}/day_{:02}.txt", year_number, day_number);
-  println!("input_path: {:?}", input_path);
+  match (command, positional.as_slice()) {
+    ("config", ["show"]) => config.show(),
+    ("graph", [year, day]) => {
+      let (year, day) = parse_puzzle(&mut config, Some(year), day);
+      runner::write_graph(year, day, &config, graph_out.map(Path::new))
+    }
+    ("visualize", [year, day]) => {
+      let (year, day) = parse_puzzle(&mut config, Some(year), day);
+      runner::visualize(year, day, &config, fps, frame_dump.map(Path::new))
+    }
+    ("trace", [year, day]) => {
+      let (year, day) = parse_puzzle(&mut config, Some(year), day);
+      runner::trace(year, day, &config, trace_cycles)
+    }
+    ("run", [year, day]) => run(&mut config, Some(year), day, inputs_dir),
+    ("run", [day]) => run(&mut config, None, day, inputs_dir),
+    ("run", []) => {
+      let (year, day, reason) = runner::infer_puzzle(SystemTime::now());
+      match config.output_format {
+        OutputFormat::Text => println!("Running {} day {}: {}", year, day, reason),
+        OutputFormat::Json => eprintln!("Running {} day {}: {}", year, day, reason),
+      }
+      run(&mut config, Some(&year.to_string()), &day.to_string(), inputs_dir)
+    }
+    _ => exit_with(USAGE),
+  }
+}
 
-  let input_contents =
-    fs::read_to_string(input_path).expect("Should have been able to read the file at path {:?}");
+fn run(config: &mut Config, year: Option<&str>, day: &str, inputs_dir: Option<&str>) {
+  let (year, day) = parse_puzzle(config, year, day);
+  match inputs_dir {
+    Some(dir) => runner::run_inputs_dir(year, day, Path::new(dir), config),
+    None => runner::run_day(year, day, config),
+  }
+}
 
-  let puzzle_input: Vec<&str> = input_contents.split(['\n']).map(|e| e.trim()).collect();
+/// Parses the year/day a command was given, falling back to the default
+/// year from aoc.toml when there isn't one.
+fn parse_puzzle(config: &mut Config, year: Option<&str>, day: &str) -> (u32, u32) {
+  if let Some(year) = year {
+    config.apply_flag("--year", year).unwrap_or_else(|e| exit_with(&e));
+  }
+  let Some(year) = config.year else {
+    exit_with("No year passed and no default year set in aoc.toml");
+  };
+  let day = day.parse::<u32>().unwrap_or_else(|_| exit_with(USAGE));
+  (year, day)
+}
 
-  match year_number {
-    2018 => aoc_2018::run_day_number(day_number, &puzzle_input),
-    2019 => aoc_2019::run_day_number(day_number, &puzzle_input),
-    2020 => aoc_2020::run_day_number(day_number, &puzzle_input),
-    2021 => aoc_2021::run_day_number(day_number, &puzzle_input),
-    2022 => aoc_2022::run_day_number(day_number, &puzzle_input),
-    2023 => aoc_2023::run_day_number(day_number, &puzzle_input),
-    _ => panic!("Invalid year number passed: [{:?}]", year_number),
+// `20-60`, or a single cycle like `20`
+fn parse_cycles(value: &str) -> RangeInclusive<u64> {
+  let (first, last) = value.split_once('-').unwrap_or((value, value));
+  match (first.trim().parse(), last.trim().parse()) {
+    (Ok(first), Ok(last)) if first <= last => first..=last,
+    _ => exit_with(&format!("Invalid --cycles '{}', expected <first>-<last>", value)),
   }
 }
+
+fn exit_with(message: &str) -> ! {
+  eprintln!("{}", message);
+  process::exit(1);
+}
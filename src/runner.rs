@@ -0,0 +1,482 @@
+use std::{
+  fs,
+  ops::RangeInclusive,
+  panic::{self, AssertUnwindSafe},
+  path::{Path, PathBuf},
+  sync::{mpsc, Arc},
+  thread,
+  time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use itertools::Itertools;
+
+use crate::{
+  aoc_2018, aoc_2019, aoc_2020, aoc_2021, aoc_2022, aoc_2023,
+  config::{Config, OutputFormat},
+  utils::{
+    animation::{self, Frame},
+    dot::Graph,
+  },
+};
+
+/// Puzzles unlock at midnight US Eastern (UTC-5, no DST in December).
+const PUZZLE_RELEASE_UTC_OFFSET_SECS: i64 = -5 * 60 * 60;
+
+/// Every year with a module, newest first.
+const REGISTERED_YEARS: [u32; 6] = [2023, 2022, 2021, 2020, 2019, 2018];
+
+/// Solvers recurse pretty deep on some inputs, so give each one plenty of stack.
+const SOLVER_STACK_SIZE: usize = 64 * 1024 * 1024;
+
+/// A solver takes the split puzzle input and returns its answer already
+/// formatted for display.
+pub type Solver = fn(&Vec<&str>) -> String;
+
+/// One registered puzzle part for a day, e.g. "Steps to ZZZ".
+pub struct Part {
+  pub label: &'static str,
+  pub solve: Solver,
+}
+
+/// Builds a Graphviz graph of a day's input, for puzzles where seeing the
+/// input's shape is most of the battle.
+pub type GraphExporter = fn(&Vec<&str>) -> Graph;
+
+/// Steps through a day's simulation, returning a frame for each step worth
+/// looking at.
+pub type Visualizer = fn(&Vec<&str>) -> Vec<Frame>;
+
+/// Renders a table of a day's execution step by step, optionally limited
+/// to an inclusive range of steps.
+pub type Tracer = fn(&Vec<&str>, Option<RangeInclusive<u64>>) -> String;
+
+/// The outcome of running a single part. A part that panics or runs past
+/// the timeout is recorded as an `Err` instead of taking down the run.
+pub struct PartResult {
+  pub label: &'static str,
+  pub answer: Result<String, String>,
+  pub elapsed: Duration,
+}
+
+/// Looks up every registered part for a given year/day.
+pub fn parts_for(year: u32, day: u32) -> Option<Vec<Part>> {
+  match year {
+    2018 => aoc_2018::day_parts(day),
+    2019 => aoc_2019::day_parts(day),
+    2020 => aoc_2020::day_parts(day),
+    2021 => aoc_2021::day_parts(day),
+    2022 => aoc_2022::day_parts(day),
+    2023 => aoc_2023::day_parts(day),
+    _ => None,
+  }
+}
+
+/// Looks up the graph export hook for a given year/day, if it has one.
+pub fn graph_for(year: u32, day: u32) -> Option<GraphExporter> {
+  match year {
+    2023 => aoc_2023::day_graph(day),
+    _ => None,
+  }
+}
+
+/// Looks up the visualisation hook for a given year/day, if it has one.
+pub fn visualizer_for(year: u32, day: u32) -> Option<Visualizer> {
+  match year {
+    2023 => aoc_2023::day_visualizer(day),
+    _ => None,
+  }
+}
+
+/// Looks up the trace hook for a given year/day, if it has one.
+pub fn tracer_for(year: u32, day: u32) -> Option<Tracer> {
+  match year {
+    2022 => aoc_2022::day_tracer(day),
+    _ => None,
+  }
+}
+
+/// Picks the puzzle to run when none is given. During December 1-25 (in
+/// the puzzle-release timezone) that's today's puzzle, as long as it has
+/// been registered, otherwise the most recently registered day. Returns
+/// the year, day and a short explanation of the choice.
+pub fn infer_puzzle(now: SystemTime) -> (u32, u32, String) {
+  let (year, month, day) = release_date(now);
+  if month == 12 && (1..=25).contains(&day) {
+    if parts_for(year, day).is_some() {
+      return (year, day, format!("today's puzzle ({}-12-{:02})", year, day));
+    }
+    let (latest_year, latest_day) = latest_registered_day();
+    let reason = format!("today's puzzle ({}-12-{:02}) isn't registered, using latest registered day", year, day);
+    return (latest_year, latest_day, reason);
+  }
+
+  let (latest_year, latest_day) = latest_registered_day();
+  (latest_year, latest_day, "latest registered day, outside of the event".to_owned())
+}
+
+/// The newest year/day pair that has parts registered.
+pub fn latest_registered_day() -> (u32, u32) {
+  REGISTERED_YEARS
+    .iter()
+    .flat_map(|year| (1..=25).rev().map(move |day| (*year, day)))
+    .find(|(year, day)| parts_for(*year, *day).is_some())
+    .expect("No days registered")
+}
+
+/// Splits raw file contents into trimmed lines, the shape every solver expects.
+pub fn split_input(contents: &str) -> Vec<&str> {
+  contents.split(['\n']).map(|e| e.trim()).collect()
+}
+
+/// Runs each part against the input on its own thread, timing it and
+/// catching any panic. A part still going after `timeout` is abandoned
+/// and recorded as failed.
+pub fn solve_parts(parts: &[Part], contents: &Arc<String>, timeout: Option<Duration>) -> Vec<PartResult> {
+  parts
+    .iter()
+    .map(|part| {
+      let (sender, receiver) = mpsc::channel();
+      let solve = part.solve;
+      let contents = Arc::clone(contents);
+      let start = Instant::now();
+
+      thread::Builder::new()
+        .stack_size(SOLVER_STACK_SIZE)
+        .spawn(move || {
+          let input = split_input(&contents);
+          let answer = panic::catch_unwind(AssertUnwindSafe(|| solve(&input)))
+            .map_err(|payload| panic_message(payload.as_ref()));
+          let _ = sender.send(answer);
+        })
+        .expect("Unable to spawn solver thread");
+
+      let answer = match timeout {
+        Some(limit) => receiver
+          .recv_timeout(limit)
+          .unwrap_or_else(|_| Err(format!("timed out after {:?}", limit))),
+        None => receiver.recv().unwrap_or_else(|_| Err("solver thread died".to_owned())),
+      };
+
+      PartResult {
+        label: part.label,
+        answer,
+        elapsed: start.elapsed(),
+      }
+    })
+    .collect_vec()
+}
+
+/// Runs a single day against its configured input and prints each answer.
+pub fn run_day(year: u32, day: u32, config: &Config) {
+  let Some(parts) = parts_for(year, day) else {
+    println!("Code for day {:?} undefined", day);
+    return;
+  };
+
+  let input_path = config.input_path(year, day);
+  if config.output_format == OutputFormat::Text {
+    println!("input_path: {:?}", input_path);
+  }
+
+  let contents = Arc::new(read_input(config, year, day));
+  let results = with_quiet_panics(|| solve_parts(&parts, &contents, config.timeout));
+
+  match config.output_format {
+    OutputFormat::Text => {
+      let label_width = parts.iter().map(|p| p.label.len()).max().unwrap_or(0);
+      println!("\nDay {}:", day);
+      for result in results {
+        let label = format!("{}:", result.label);
+        match result.answer {
+          Ok(answer) => println!("  {:<w$} {}", label, answer, w = label_width + 1),
+          Err(msg) => println!("  {:<w$} FAILED ({})", label, msg, w = label_width + 1),
+        }
+      }
+    }
+    OutputFormat::Json => println!("{}", results_json(year, day, None, &results)),
+  }
+}
+
+/// Runs a single day against every file in `dir`, then prints a table
+/// with one row per input and the answer + timing of each part side by
+/// side. Inputs where a part failed are highlighted in red. Up to
+/// `config.threads` inputs are solved at once.
+pub fn run_inputs_dir(year: u32, day: u32, dir: &Path, config: &Config) {
+  let Some(parts) = parts_for(year, day) else {
+    println!("Code for day {:?} undefined", day);
+    return;
+  };
+
+  let input_paths = fs::read_dir(dir)
+    .unwrap_or_else(|e| panic!("Unable to read inputs dir {:?}: {}", dir, e))
+    .map(|entry| entry.unwrap().path())
+    .filter(|path| path.is_file())
+    .sorted()
+    .collect_vec();
+
+  let mut inputs: Vec<(String, Arc<String>)> = Vec::new();
+  for path in input_paths {
+    let name = path.file_name().unwrap().to_string_lossy().to_string();
+    match fs::read_to_string(&path) {
+      Ok(contents) => inputs.push((name, Arc::new(contents))),
+      Err(e) => println!("Skipping {}: {}", name, e),
+    }
+  }
+
+  let rows = with_quiet_panics(|| {
+    let mut rows: Vec<(String, Vec<PartResult>)> = Vec::new();
+    for batch in inputs.chunks(config.threads) {
+      let batch_results = thread::scope(|scope| {
+        batch
+          .iter()
+          .map(|(name, contents)| {
+            let parts = &parts;
+            scope.spawn(move || (name.clone(), solve_parts(parts, contents, config.timeout)))
+          })
+          .collect_vec()
+          .into_iter()
+          .map(|handle| handle.join().unwrap())
+          .collect_vec()
+      });
+      rows.extend(batch_results);
+    }
+    rows
+  });
+
+  match config.output_format {
+    OutputFormat::Text => print_results_table(year, day, &parts, &rows),
+    OutputFormat::Json => {
+      let entries = rows
+        .iter()
+        .map(|(name, results)| results_json(year, day, Some(name), results))
+        .join(",\n  ");
+      println!("[\n  {}\n]", entries);
+    }
+  }
+}
+
+/// Exports a day's input as a DOT file, by default `<year>_day_<NN>.dot`
+/// in the working directory.
+pub fn write_graph(year: u32, day: u32, config: &Config, out: Option<&Path>) {
+  let Some(export) = graph_for(year, day) else {
+    println!("No graph export for {} day {:?}", year, day);
+    return;
+  };
+
+  let contents = read_input(config, year, day);
+  let graph = export(&split_input(&contents));
+
+  let out_path = out.map_or_else(|| PathBuf::from(format!("{}_day_{:02}.dot", year, day)), Path::to_path_buf);
+  fs::write(&out_path, format!("{}\n", graph))
+    .unwrap_or_else(|e| panic!("Unable to write graph to {:?}: {}", out_path, e));
+  println!(
+    "Wrote {} nodes and {} edges to {:?}",
+    graph.node_count(),
+    graph.edge_count(),
+    out_path
+  );
+}
+
+/// Animates a day's simulation in the terminal at `fps`, or writes every
+/// frame to `dump` as plain text instead.
+pub fn visualize(year: u32, day: u32, config: &Config, fps: f64, dump: Option<&Path>) {
+  let Some(visualizer) = visualizer_for(year, day) else {
+    println!("No visualisation for {} day {:?}", year, day);
+    return;
+  };
+
+  let contents = read_input(config, year, day);
+  let frames = visualizer(&split_input(&contents));
+
+  match dump {
+    Some(path) => {
+      let mut file = fs::File::create(path).unwrap_or_else(|e| panic!("Unable to create {:?}: {}", path, e));
+      animation::dump_frames(&frames, &mut file).unwrap_or_else(|e| panic!("Unable to write frames to {:?}: {}", path, e));
+      println!("Wrote {} frames to {:?}", frames.len(), path);
+    }
+    None => animation::play(&frames, fps),
+  }
+}
+
+/// Prints a day's execution trace, limited to `steps` if given.
+pub fn trace(year: u32, day: u32, config: &Config, steps: Option<RangeInclusive<u64>>) {
+  let Some(tracer) = tracer_for(year, day) else {
+    println!("No trace for {} day {:?}", year, day);
+    return;
+  };
+
+  let contents = read_input(config, year, day);
+  println!("{}", tracer(&split_input(&contents), steps));
+}
+
+// Helpers
+
+/// Reads a day's input from the configured input root, panicking if it
+/// isn't there.
+fn read_input(config: &Config, year: u32, day: u32) -> String {
+  let input_path = config.input_path(year, day);
+  fs::read_to_string(&input_path)
+    .unwrap_or_else(|e| panic!("Should have been able to read the file at path {:?}: {}", input_path, e))
+}
+
+/// Calendar (year, month, day) at the puzzle-release timezone for a given time.
+fn release_date(now: SystemTime) -> (u32, u32, u32) {
+  let secs = now.duration_since(UNIX_EPOCH).unwrap().as_secs() as i64 + PUZZLE_RELEASE_UTC_OFFSET_SECS;
+  civil_from_days(secs.div_euclid(86_400))
+}
+
+/// Converts days since 1970-01-01 to a (year, month, day) date, see
+/// http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> (u32, u32, u32) {
+  let z = days + 719_468;
+  let era = z.div_euclid(146_097);
+  let doe = z.rem_euclid(146_097);
+  let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+  let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+  let mp = (5 * doy + 2) / 153;
+  let day = doy - (153 * mp + 2) / 5 + 1;
+  let month = if mp < 10 { mp + 3 } else { mp - 9 };
+  let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+  (year as u32, month as u32, day as u32)
+}
+
+fn print_results_table(year: u32, day: u32, parts: &[Part], rows: &[(String, Vec<PartResult>)]) {
+  let cell = |result: &PartResult| match &result.answer {
+    Ok(answer) => format!("{} ({:.2?})", answer, result.elapsed),
+    Err(_) => "FAILED".to_owned(),
+  };
+
+  let mut headers = vec!["Input".to_owned()];
+  headers.extend(parts.iter().map(|p| p.label.to_owned()));
+
+  let mut widths = headers.iter().map(|h| h.len()).collect_vec();
+  for (name, results) in rows {
+    widths[0] = widths[0].max(name.len());
+    for (i, result) in results.iter().enumerate() {
+      widths[i + 1] = widths[i + 1].max(cell(result).len());
+    }
+  }
+
+  println!("\n{} Day {} across {} inputs:", year, day, rows.len());
+  let header_row = headers
+    .iter()
+    .zip(&widths)
+    .map(|(h, w)| format!("{:<w$}", h, w = w))
+    .join(" | ");
+  println!("  {}", header_row.trim_end());
+  println!("  {}", widths.iter().map(|w| "-".repeat(*w)).join("-+-"));
+
+  for (name, results) in rows {
+    let failed = results.iter().any(|r| r.answer.is_err());
+    let mut cells = vec![format!("{:<w$}", name, w = widths[0])];
+    cells.extend(
+      results
+        .iter()
+        .enumerate()
+        .map(|(i, r)| format!("{:<w$}", cell(r), w = widths[i + 1])),
+    );
+    let line = cells.join(" | ").trim_end().to_owned();
+    if failed {
+      println!("  \x1b[31m{}\x1b[0m", line);
+    } else {
+      println!("  {}", line);
+    }
+  }
+
+  for (name, results) in rows {
+    for result in results.iter().filter(|r| r.answer.is_err()) {
+      let msg = result.answer.as_ref().unwrap_err();
+      println!("  {} / {}: {}", name, result.label, msg);
+    }
+  }
+}
+
+fn results_json(year: u32, day: u32, input: Option<&str>, results: &[PartResult]) -> String {
+  let parts = results
+    .iter()
+    .map(|r| {
+      let (answer, error) = match &r.answer {
+        Ok(answer) => (json_string(answer), "null".to_owned()),
+        Err(msg) => ("null".to_owned(), json_string(msg)),
+      };
+      format!(
+        "{{\"label\": {}, \"answer\": {}, \"error\": {}, \"elapsed_ms\": {:.3}}}",
+        json_string(r.label),
+        answer,
+        error,
+        r.elapsed.as_secs_f64() * 1000.0
+      )
+    })
+    .join(", ");
+
+  let input = input.map_or(String::new(), |name| format!("\"input\": {}, ", json_string(name)));
+  format!("{{{}\"year\": {}, \"day\": {}, \"parts\": [{}]}}", input, year, day, parts)
+}
+
+fn json_string(s: &str) -> String {
+  let mut out = String::from("\"");
+  for c in s.chars() {
+    match c {
+      '"' => out.push_str("\\\""),
+      '\\' => out.push_str("\\\\"),
+      '\n' => out.push_str("\\n"),
+      c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+      c => out.push(c),
+    }
+  }
+  out.push('"');
+  out
+}
+
+/// Swaps in a no-op panic hook while `f` runs so failing parts don't
+/// spray backtraces over the results.
+fn with_quiet_panics<T>(f: impl FnOnce() -> T) -> T {
+  let default_hook = panic::take_hook();
+  panic::set_hook(Box::new(|_| {}));
+  let out = f();
+  panic::set_hook(default_hook);
+  out
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+  if let Some(msg) = payload.downcast_ref::<&str>() {
+    return msg.to_string();
+  }
+  if let Some(msg) = payload.downcast_ref::<String>() {
+    return msg.clone();
+  }
+  "panicked".to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn at(secs: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(secs)
+  }
+
+  #[test]
+  fn release_date_uses_eastern_time() {
+    // 2023-12-05T04:59:59Z is still Dec 4th in the puzzle timezone, a second later it's the 5th
+    assert_eq!(release_date(at(1_701_752_399)), (2023, 12, 4));
+    assert_eq!(release_date(at(1_701_752_400)), (2023, 12, 5));
+  }
+
+  #[test]
+  fn infers_todays_puzzle_in_december() {
+    let (year, day, _) = infer_puzzle(at(1_701_752_400));
+    assert_eq!((year, day), (2023, 5));
+  }
+
+  #[test]
+  fn falls_back_to_latest_registered_day() {
+    // Nothing is registered for 2023-12-25 or for any day in July
+    let expected = latest_registered_day();
+    assert_eq!(release_date(at(1_703_480_400)), (2023, 12, 25));
+    let (year, day, _) = infer_puzzle(at(1_703_480_400));
+    assert_eq!((year, day), expected);
+    let (year, day, _) = infer_puzzle(at(1_688_990_400));
+    assert_eq!((year, day), expected);
+  }
+}
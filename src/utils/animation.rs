@@ -0,0 +1,225 @@
+#![allow(dead_code)]
+
+use std::{
+  fmt::Write as _,
+  io::{self, BufRead, Write},
+  sync::mpsc::{self, Receiver, RecvTimeoutError},
+  thread,
+  time::Duration,
+};
+
+use super::grid::{Coord, Grid};
+
+/// The fastest `play` will go, however many times `+` is pressed.
+const MIN_FRAME_DELAY: Duration = Duration::from_millis(1);
+
+/// Foreground colours for animated cells, using the basic 16-colour ANSI
+/// set so they work in any terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+  Red,
+  Green,
+  Yellow,
+  Blue,
+  Magenta,
+  Cyan,
+  White,
+  Grey,
+}
+
+impl Color {
+  fn ansi_code(&self) -> u8 {
+    match self {
+      Color::Red => 91,
+      Color::Green => 92,
+      Color::Yellow => 93,
+      Color::Blue => 94,
+      Color::Magenta => 95,
+      Color::Cyan => 96,
+      Color::White => 97,
+      Color::Grey => 90,
+    }
+  }
+}
+
+/// One character of a frame, optionally coloured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+  pub ch: char,
+  pub color: Option<Color>,
+}
+
+impl Cell {
+  pub fn plain(ch: char) -> Cell {
+    Cell { ch, color: None }
+  }
+
+  pub fn colored(ch: char, color: Color) -> Cell {
+    Cell { ch, color: Some(color) }
+  }
+}
+
+/// A single snapshot of a simulation with a caption underneath, e.g. the
+/// step number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+  pub caption: String,
+  pub cells: Grid<Cell>,
+}
+
+impl Frame {
+  /// Builds a frame by styling every cell of `grid`.
+  pub fn from_grid<T>(grid: &Grid<T>, caption: impl Into<String>, style: impl Fn(Coord, &T) -> Cell) -> Frame {
+    let rows = grid
+      .rows()
+      .enumerate()
+      .map(|(row, cells)| {
+        cells
+          .iter()
+          .enumerate()
+          .map(|(col, value)| style((row as isize, col as isize), value))
+          .collect()
+      })
+      .collect();
+    Frame {
+      caption: caption.into(),
+      cells: Grid::from_rows(rows),
+    }
+  }
+
+  /// The frame with ANSI colour codes, for a terminal.
+  pub fn render_ansi(&self) -> String {
+    self.render(true)
+  }
+
+  /// The frame as plain text, for dumping to a file.
+  pub fn render_plain(&self) -> String {
+    self.render(false)
+  }
+
+  // Helpers
+  fn render(&self, ansi: bool) -> String {
+    let mut out = String::new();
+    for row in self.cells.rows() {
+      let mut current: Option<Color> = None;
+      for cell in row {
+        if ansi && cell.color != current {
+          match cell.color {
+            Some(color) => write!(out, "\x1b[{}m", color.ansi_code()).unwrap(),
+            None => out.push_str("\x1b[0m"),
+          }
+          current = cell.color;
+        }
+        out.push(cell.ch);
+      }
+      if ansi && current.is_some() {
+        out.push_str("\x1b[0m");
+      }
+      out.push('\n');
+    }
+    out.push_str(&self.caption);
+    out
+  }
+}
+
+/// Writes every frame as plain text, separated by blank lines, so the
+/// whole run can be read through or shared without a terminal.
+pub fn dump_frames(frames: &[Frame], out: &mut impl Write) -> io::Result<()> {
+  for (i, frame) in frames.iter().enumerate() {
+    writeln!(out, "--- frame {}/{} ---", i + 1, frames.len())?;
+    writeln!(out, "{}\n", frame.render_plain())?;
+  }
+  Ok(())
+}
+
+/// Plays frames in the terminal at `fps` frames a second. Typing a line
+/// on stdin controls playback:
+///
+/// - Enter pauses and resumes
+/// - `+` / `-` doubles or halves the speed, up to 1000 fps
+/// - `q` stops
+pub fn play(frames: &[Frame], fps: f64) {
+  let controls = spawn_controls();
+  let mut delay = Duration::from_secs_f64(1.0 / fps.max(0.001)).max(MIN_FRAME_DELAY);
+  let mut paused = false;
+  let mut index = 0;
+
+  while index < frames.len() {
+    print!("\x1b[2J\x1b[H{}", frames[index].render_ansi());
+    println!(
+      "\n\x1b[90m[{}/{}] {:.1} fps{}  (enter: pause, +/-: speed, q: quit)\x1b[0m",
+      index + 1,
+      frames.len(),
+      1.0 / delay.as_secs_f64(),
+      if paused { ", paused" } else { "" }
+    );
+    let _ = io::stdout().flush();
+
+    // Block on input while paused, otherwise only wait out the frame. With
+    // no stdin to read from, just keep playing at the current speed.
+    let command = match paused {
+      true => controls.recv().ok().or_else(|| {
+        paused = false;
+        None
+      }),
+      false => match controls.recv_timeout(delay) {
+        Ok(line) => Some(line),
+        Err(RecvTimeoutError::Timeout) => None,
+        Err(RecvTimeoutError::Disconnected) => {
+          thread::sleep(delay);
+          None
+        }
+      },
+    };
+    match command.as_deref().map(str::trim) {
+      Some("") => paused = !paused,
+      Some("+") => delay = (delay / 2).max(MIN_FRAME_DELAY),
+      Some("-") => delay *= 2,
+      Some("q") => break,
+      _ if !paused => index += 1,
+      _ => {}
+    }
+  }
+}
+
+// Helpers
+fn spawn_controls() -> Receiver<String> {
+  let (sender, receiver) = mpsc::channel();
+  thread::spawn(move || {
+    for line in io::stdin().lock().lines().map_while(Result::ok) {
+      if sender.send(line).is_err() {
+        break;
+      }
+    }
+  });
+  receiver
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sample() -> Frame {
+    let grid = Grid::parse(&["#.O", "..O"]);
+    Frame::from_grid(&grid, "step 1", |_, &ch| match ch {
+      'O' => Cell::colored('O', Color::Yellow),
+      _ => Cell::plain(ch),
+    })
+  }
+
+  #[test]
+  fn renders_plain_and_ansi() {
+    let frame = sample();
+    assert_eq!(frame.render_plain(), "#.O\n..O\nstep 1");
+    assert_eq!(frame.render_ansi(), "#.\x1b[93mO\x1b[0m\n..\x1b[93mO\x1b[0m\nstep 1");
+  }
+
+  #[test]
+  fn dumps_every_frame() {
+    let mut out = Vec::new();
+    dump_frames(&[sample(), sample()], &mut out).unwrap();
+    let text = String::from_utf8(out).unwrap();
+    assert!(text.starts_with("--- frame 1/2 ---\n#.O\n..O\nstep 1\n\n"));
+    assert!(text.contains("--- frame 2/2 ---"));
+  }
+}
@@ -0,0 +1,288 @@
+#![allow(dead_code)]
+
+use std::{
+  fmt,
+  ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, Not},
+};
+
+use super::{
+  geometry::Direction,
+  grid::{Coord, Grid},
+};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A rectangular set of cells packed one bit per cell, each row padded out
+/// to whole `u64` words. Set operations and shifts work a word at a time,
+/// which makes whole-map simulation steps far cheaper than walking a
+/// `HashSet` of positions.
+///
+/// Bits past the right edge are always kept clear, so equality, hashing
+/// and `count_ones` only ever see real cells.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+  width: usize,
+  height: usize,
+  words_per_row: usize,
+  words: Vec<u64>,
+}
+
+impl BitGrid {
+  /// An empty grid, every cell clear.
+  pub fn new(width: usize, height: usize) -> BitGrid {
+    let words_per_row = width.div_ceil(WORD_BITS);
+    BitGrid {
+      width,
+      height,
+      words_per_row,
+      words: vec![0; words_per_row * height],
+    }
+  }
+
+  /// Sets every cell of `grid` that matches `pred`.
+  pub fn from_grid<T>(grid: &Grid<T>, pred: impl Fn(&T) -> bool) -> BitGrid {
+    let mut bits = BitGrid::new(grid.width(), grid.height());
+    for (pos, value) in grid.iter() {
+      if pred(value) {
+        bits.set(pos, true);
+      }
+    }
+    bits
+  }
+
+  pub fn width(&self) -> usize {
+    self.width
+  }
+
+  pub fn height(&self) -> usize {
+    self.height
+  }
+
+  pub fn in_bounds(&self, pos: impl Into<Coord>) -> bool {
+    let (row, col) = pos.into();
+    row >= 0 && col >= 0 && (row as usize) < self.height && (col as usize) < self.width
+  }
+
+  /// Whether the cell is set. Anything off the grid counts as clear.
+  pub fn get(&self, pos: impl Into<Coord>) -> bool {
+    let pos = pos.into();
+    if !self.in_bounds(pos) {
+      return false;
+    }
+    let (word, bit) = self.locate(pos);
+    self.words[word] >> bit & 1 == 1
+  }
+
+  /// Sets or clears a cell. Returns false (and does nothing) if it's off
+  /// the grid.
+  pub fn set(&mut self, pos: impl Into<Coord>, value: bool) -> bool {
+    let pos = pos.into();
+    if !self.in_bounds(pos) {
+      return false;
+    }
+    let (word, bit) = self.locate(pos);
+    match value {
+      true => self.words[word] |= 1 << bit,
+      false => self.words[word] &= !(1 << bit),
+    }
+    true
+  }
+
+  pub fn count_ones(&self) -> usize {
+    self.words.iter().map(|w| w.count_ones() as usize).sum()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.words.iter().all(|&w| w == 0)
+  }
+
+  /// Positions of every set cell in row-major order.
+  pub fn ones(&self) -> impl Iterator<Item = Coord> + '_ {
+    self.words.iter().enumerate().flat_map(move |(i, &word)| {
+      let row = i / self.words_per_row;
+      let base = (i % self.words_per_row) * WORD_BITS;
+      (0..WORD_BITS)
+        .filter(move |bit| word >> bit & 1 == 1)
+        .map(move |bit| (row as isize, (base + bit) as isize))
+    })
+  }
+
+  /// Every cell moved one step in `direction`. Cells pushed off the edge
+  /// are lost and the row or column uncovered is left clear.
+  pub fn shifted(&self, direction: Direction) -> BitGrid {
+    let mut out = BitGrid::new(self.width, self.height);
+    if self.words.is_empty() {
+      return out;
+    }
+    let row_len = self.words_per_row;
+    // Number of words in every row but one
+    let rest = self.words.len().saturating_sub(row_len);
+    match direction {
+      Direction::North => out.words[..rest].copy_from_slice(&self.words[row_len..]),
+      Direction::South => out.words[row_len..].copy_from_slice(&self.words[..rest]),
+      // Columns are bits, so east is towards the high end of each word
+      Direction::East => {
+        for (src, dst) in self.words.chunks(row_len).zip(out.words.chunks_mut(row_len)) {
+          let mut carry = 0;
+          for (s, d) in src.iter().zip(dst.iter_mut()) {
+            *d = s << 1 | carry;
+            carry = s >> (WORD_BITS - 1);
+          }
+        }
+        out.clear_padding();
+      }
+      Direction::West => {
+        for (src, dst) in self.words.chunks(row_len).zip(out.words.chunks_mut(row_len)) {
+          let mut carry = 0;
+          for (s, d) in src.iter().zip(dst.iter_mut()).rev() {
+            *d = s >> 1 | carry;
+            carry = s << (WORD_BITS - 1);
+          }
+        }
+      }
+    }
+    out
+  }
+
+  /// Every cell orthogonally next to a set cell, not counting the set
+  /// cells themselves unless they neighbour one another.
+  pub fn neighbours4(&self) -> BitGrid {
+    Direction::ALL
+      .into_iter()
+      .fold(BitGrid::new(self.width, self.height), |acc, dir| acc | self.shifted(dir))
+  }
+
+  // Helpers
+  fn locate(&self, (row, col): Coord) -> (usize, usize) {
+    let (row, col) = (row as usize, col as usize);
+    (row * self.words_per_row + col / WORD_BITS, col % WORD_BITS)
+  }
+
+  fn clear_padding(&mut self) {
+    let used = self.width % WORD_BITS;
+    if used == 0 {
+      return;
+    }
+    let mask = (1 << used) - 1;
+    for row in self.words.chunks_mut(self.words_per_row) {
+      *row.last_mut().unwrap() &= mask;
+    }
+  }
+
+  fn combine(&mut self, other: &BitGrid, f: impl Fn(u64, u64) -> u64) {
+    assert!(
+      self.width == other.width && self.height == other.height,
+      "BitGrid size mismatch: {}x{} vs {}x{}",
+      self.width,
+      self.height,
+      other.width,
+      other.height
+    );
+    for (a, &b) in self.words.iter_mut().zip(&other.words) {
+      *a = f(*a, b);
+    }
+  }
+}
+
+impl BitAnd for BitGrid {
+  type Output = BitGrid;
+  fn bitand(mut self, other: BitGrid) -> BitGrid {
+    self.combine(&other, |a, b| a & b);
+    self
+  }
+}
+
+impl BitOr for BitGrid {
+  type Output = BitGrid;
+  fn bitor(mut self, other: BitGrid) -> BitGrid {
+    self.combine(&other, |a, b| a | b);
+    self
+  }
+}
+
+impl BitXor for BitGrid {
+  type Output = BitGrid;
+  fn bitxor(mut self, other: BitGrid) -> BitGrid {
+    self.combine(&other, |a, b| a ^ b);
+    self
+  }
+}
+
+impl BitAndAssign<&BitGrid> for BitGrid {
+  fn bitand_assign(&mut self, other: &BitGrid) {
+    self.combine(other, |a, b| a & b);
+  }
+}
+
+impl BitOrAssign<&BitGrid> for BitGrid {
+  fn bitor_assign(&mut self, other: &BitGrid) {
+    self.combine(other, |a, b| a | b);
+  }
+}
+
+impl Not for BitGrid {
+  type Output = BitGrid;
+  fn not(mut self) -> BitGrid {
+    self.words.iter_mut().for_each(|w| *w = !*w);
+    self.clear_padding();
+    self
+  }
+}
+
+impl fmt::Display for BitGrid {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    for row in 0..self.height as isize {
+      if row > 0 {
+        writeln!(f)?;
+      }
+      for col in 0..self.width as isize {
+        write!(f, "{}", if self.get((row, col)) { '#' } else { '.' })?;
+      }
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn set_get_and_count() {
+    let mut bits = BitGrid::new(70, 3);
+    assert!(bits.set((1, 65), true));
+    assert!(bits.set((2, 0), true));
+    assert!(!bits.set((3, 0), true));
+    assert!(bits.get((1, 65)));
+    assert!(!bits.get((1, 64)));
+    assert!(!bits.get((-1, 0)));
+    assert_eq!(bits.count_ones(), 2);
+    assert_eq!(bits.ones().collect::<Vec<_>>(), vec![(1, 65), (2, 0)]);
+    assert_eq!((!bits).count_ones(), 70 * 3 - 2);
+  }
+
+  #[test]
+  fn shifts_across_words() {
+    let mut bits = BitGrid::new(70, 2);
+    bits.set((0, 63), true);
+    bits.set((1, 69), true);
+
+    let east = bits.shifted(Direction::East);
+    assert_eq!(east.ones().collect::<Vec<_>>(), vec![(0, 64)]);
+    let west = bits.shifted(Direction::West);
+    assert_eq!(west.ones().collect::<Vec<_>>(), vec![(0, 62), (1, 68)]);
+    let north = bits.shifted(Direction::North);
+    assert_eq!(north.ones().collect::<Vec<_>>(), vec![(0, 69)]);
+    let south = bits.shifted(Direction::South);
+    assert_eq!(south.ones().collect::<Vec<_>>(), vec![(1, 63)]);
+  }
+
+  #[test]
+  fn spreads_to_neighbours() {
+    let grid = Grid::parse(&["...", ".#.", "..."]);
+    let centre = BitGrid::from_grid(&grid, |&c| c == '#');
+    assert_eq!(centre.to_string(), grid.to_string());
+    assert_eq!(centre.neighbours4().to_string(), ".#.\n#.#\n.#.");
+    assert_eq!((centre.neighbours4() | centre).count_ones(), 5);
+  }
+}
@@ -0,0 +1,299 @@
+#![allow(dead_code)]
+
+use std::{
+  fmt,
+  ops::{Index, IndexMut},
+};
+
+use super::parse::ParseError;
+
+/// Register names are single lowercase letters, so `a` through `z`.
+/// Dialects with a differently named register map it onto a letter, e.g.
+/// the handheld console's accumulator lives in [`ACCUMULATOR`].
+pub type Register = char;
+
+/// Where `acc` (2020 day 8) adds to.
+pub const ACCUMULATOR: Register = 'a';
+
+/// One register per letter, all starting at 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Registers([i64; 26]);
+
+impl Index<Register> for Registers {
+  type Output = i64;
+
+  fn index(&self, register: Register) -> &i64 {
+    &self.0[slot(register)]
+  }
+}
+
+impl IndexMut<Register> for Registers {
+  fn index_mut(&mut self, register: Register) -> &mut i64 {
+    &mut self.0[slot(register)]
+  }
+}
+
+/// An instruction argument: either a register to read or a literal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+  Register(Register),
+  Value(i64),
+}
+
+impl Operand {
+  pub fn resolve(&self, registers: &Registers) -> i64 {
+    match self {
+      Operand::Register(register) => registers[*register],
+      Operand::Value(value) => *value,
+    }
+  }
+}
+
+impl fmt::Display for Operand {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Operand::Register(register) => write!(f, "{}", register),
+      Operand::Value(value) => write!(f, "{}", value),
+    }
+  }
+}
+
+/// Everything the supported dialects can do, once their mnemonics are
+/// boiled down. Jumps are relative to the jumping instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+  /// Does nothing. The argument is kept because 2020 day 8 turns `nop`s
+  /// into `jmp`s.
+  Nop(i64),
+  Add(Register, Operand),
+  Copy(Operand, Register),
+  JumpIfNonZero(Operand, Operand),
+}
+
+/// A parsed line of a program: what it does, how many cycles it takes and
+/// the text it came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Op {
+  pub instruction: Instruction,
+  pub cycles: u32,
+  pub source: String,
+}
+
+impl Op {
+  /// Parses one line in any of the supported dialects:
+  ///
+  /// - 2022 day 10: `noop`, `addx V` (two cycles, adds to `x`)
+  /// - 2020 day 8: `nop V`, `acc V`, `jmp V`
+  /// - 2016 assembunny: `cpy X r`, `inc r`, `dec r`, `jnz X Y`
+  ///
+  /// Everything but `addx` takes one cycle.
+  pub fn parse(text: &str, line: usize) -> Result<Op, ParseError> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let value = |i: usize| -> Result<i64, ParseError> {
+      match operand(&words, i, line)? {
+        Operand::Value(value) => Ok(value),
+        Operand::Register(_) => Err(ParseError::new(line, format!("expected a number in '{}'", text))),
+      }
+    };
+    let register = |i: usize| -> Result<Register, ParseError> {
+      match operand(&words, i, line)? {
+        Operand::Register(register) => Ok(register),
+        Operand::Value(_) => Err(ParseError::new(line, format!("expected a register in '{}'", text))),
+      }
+    };
+
+    let (instruction, cycles) = match words.first().copied() {
+      Some("noop") => (Instruction::Nop(0), 1),
+      Some("addx") => (Instruction::Add('x', Operand::Value(value(1)?)), 2),
+      Some("nop") => (Instruction::Nop(value(1)?), 1),
+      Some("acc") => (Instruction::Add(ACCUMULATOR, Operand::Value(value(1)?)), 1),
+      Some("jmp") => (Instruction::JumpIfNonZero(Operand::Value(1), Operand::Value(value(1)?)), 1),
+      Some("cpy") => (Instruction::Copy(operand(&words, 1, line)?, register(2)?), 1),
+      Some("inc") => (Instruction::Add(register(1)?, Operand::Value(1)), 1),
+      Some("dec") => (Instruction::Add(register(1)?, Operand::Value(-1)), 1),
+      Some("jnz") => (
+        Instruction::JumpIfNonZero(operand(&words, 1, line)?, operand(&words, 2, line)?),
+        1,
+      ),
+      _ => return Err(ParseError::new(line, format!("unknown instruction '{}'", text))),
+    };
+    Ok(Op {
+      instruction,
+      cycles,
+      source: text.trim().to_string(),
+    })
+  }
+}
+
+/// Parses every non-blank line of a program.
+pub fn parse_program(lines: &[&str]) -> Result<Vec<Op>, ParseError> {
+  lines
+    .iter()
+    .enumerate()
+    .filter(|(_, line)| !line.trim().is_empty())
+    .map(|(i, line)| Op::parse(line, i + 1))
+    .collect()
+}
+
+/// What an observer sees on each cycle. `registers` hold their values
+/// *during* the cycle, before the instruction in flight has finished.
+#[derive(Debug, Clone, Copy)]
+pub struct Tick<'a> {
+  /// 1-based, like the puzzles count them.
+  pub cycle: u64,
+  pub ip: usize,
+  pub op: &'a Op,
+  pub registers: &'a Registers,
+}
+
+/// Something that wants to watch every cycle of a run, e.g. a signal
+/// strength sampler or a CRT. Any `FnMut(&Tick)` closure is an observer.
+pub trait Observer {
+  fn tick(&mut self, tick: &Tick);
+}
+
+impl<F: FnMut(&Tick)> Observer for F {
+  fn tick(&mut self, tick: &Tick) {
+    self(tick)
+  }
+}
+
+/// Runs a parsed program one instruction at a time. The program halts
+/// once the instruction pointer leaves it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cpu {
+  pub program: Vec<Op>,
+  pub registers: Registers,
+  ip: isize,
+  cycle: u64,
+}
+
+impl Cpu {
+  pub fn new(program: Vec<Op>) -> Cpu {
+    Cpu {
+      program,
+      registers: Registers::default(),
+      ip: 0,
+      cycle: 0,
+    }
+  }
+
+  /// Index of the next instruction to run.
+  pub fn ip(&self) -> usize {
+    self.ip as usize
+  }
+
+  /// Cycles completed so far.
+  pub fn cycle(&self) -> u64 {
+    self.cycle
+  }
+
+  pub fn is_halted(&self) -> bool {
+    self.ip < 0 || self.ip as usize >= self.program.len()
+  }
+
+  /// Runs the next instruction, showing each of its cycles to every
+  /// observer first. Returns false, doing nothing, once halted.
+  pub fn step(&mut self, observers: &mut [&mut dyn Observer]) -> bool {
+    if self.is_halted() {
+      return false;
+    }
+    let ip = self.ip as usize;
+    let op = &self.program[ip];
+    for _ in 0..op.cycles {
+      self.cycle += 1;
+      let tick = Tick {
+        cycle: self.cycle,
+        ip,
+        op,
+        registers: &self.registers,
+      };
+      for observer in observers.iter_mut() {
+        observer.tick(&tick);
+      }
+    }
+
+    let mut jump = 1;
+    match op.instruction {
+      Instruction::Nop(_) => {}
+      Instruction::Add(register, amount) => self.registers[register] += amount.resolve(&self.registers),
+      Instruction::Copy(from, register) => self.registers[register] = from.resolve(&self.registers),
+      Instruction::JumpIfNonZero(condition, offset) => {
+        if condition.resolve(&self.registers) != 0 {
+          jump = offset.resolve(&self.registers) as isize;
+        }
+      }
+    }
+    self.ip += jump;
+    true
+  }
+
+  /// Runs until the program halts.
+  pub fn run(&mut self, observers: &mut [&mut dyn Observer]) {
+    while self.step(observers) {}
+  }
+}
+
+// Helpers
+fn slot(register: Register) -> usize {
+  match register {
+    'a'..='z' => register as usize - 'a' as usize,
+    _ => panic!("Invalid register '{}'", register),
+  }
+}
+
+fn operand(words: &[&str], i: usize, line: usize) -> Result<Operand, ParseError> {
+  let word = words
+    .get(i)
+    .ok_or_else(|| ParseError::new(line, format!("'{}' is missing argument {}", words.join(" "), i)))?;
+  match word.parse() {
+    Ok(value) => Ok(Operand::Value(value)),
+    Err(_) if word.len() == 1 && word.chars().all(|c| c.is_ascii_lowercase()) => {
+      Ok(Operand::Register(word.chars().next().unwrap()))
+    }
+    Err(_) => Err(ParseError::new(line, format!("invalid operand '{}'", word))),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::HashSet;
+
+  use super::*;
+
+  #[test]
+  fn cycles_see_registers_before_the_add_lands() {
+    let mut cpu = Cpu::new(parse_program(&["noop", "addx 3", "addx -5", ""]).unwrap());
+    cpu.registers['x'] = 1;
+    let mut seen = Vec::new();
+    cpu.run(&mut [&mut |tick: &Tick| seen.push((tick.cycle, tick.registers['x']))]);
+    assert_eq!(seen, vec![(1, 1), (2, 1), (3, 1), (4, 4), (5, 4)]);
+    assert_eq!(cpu.registers['x'], -1);
+    assert_eq!(cpu.cycle(), 5);
+  }
+
+  #[test]
+  fn runs_assembunny() {
+    let program = parse_program(&["cpy 41 a", "inc a", "inc a", "dec a", "jnz a 2", "dec a"]).unwrap();
+    let mut cpu = Cpu::new(program);
+    cpu.run(&mut []);
+    assert_eq!(cpu.registers['a'], 42);
+  }
+
+  #[test]
+  fn finds_the_handheld_loop() {
+    let lines = ["nop +0", "acc +1", "jmp +4", "acc +3", "jmp -3", "acc -99", "acc +1", "jmp -4", "acc +6"];
+    let mut cpu = Cpu::new(parse_program(&lines).unwrap());
+    let mut visited = HashSet::new();
+    while visited.insert(cpu.ip()) {
+      cpu.step(&mut []);
+    }
+    assert_eq!(cpu.registers[ACCUMULATOR], 5);
+  }
+
+  #[test]
+  fn reports_bad_lines() {
+    assert_eq!(parse_program(&["noop", "addx y"]), Err(ParseError::new(2, "expected a number in 'addx y'")));
+    assert_eq!(parse_program(&["", "mul 2"]), Err(ParseError::new(2, "unknown instruction 'mul 2'")));
+  }
+}
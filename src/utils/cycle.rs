@@ -0,0 +1,130 @@
+#![allow(dead_code)]
+
+use std::{collections::HashMap, hash::Hash};
+
+/// The shape of a sequence `x0, f(x0), f(f(x0)), ...` that eventually
+/// repeats: the first `start` states are seen once, then every state from
+/// there on recurs every `period` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+  pub start: usize,
+  pub period: usize,
+}
+
+impl Cycle {
+  /// The earliest step whose state matches the state at step `n`.
+  pub fn equivalent_step(&self, n: usize) -> usize {
+    match n < self.start {
+      true => n,
+      false => self.start + (n - self.start) % self.period,
+    }
+  }
+
+  /// The state at step `n`, found by replaying at most `start + period`
+  /// steps from `initial`.
+  pub fn nth_state<S: Clone>(&self, initial: &S, mut step: impl FnMut(&S) -> S, n: usize) -> S {
+    (0..self.equivalent_step(n)).fold(initial.clone(), |state, _| step(&state))
+  }
+}
+
+/// Finds the cycle by remembering every state seen. Fast, but holds the
+/// whole prefix and one full period in memory. Never returns if the
+/// sequence doesn't repeat.
+pub fn find_cycle<S: Clone + Eq + Hash>(initial: &S, mut step: impl FnMut(&S) -> S) -> Cycle {
+  let mut seen: HashMap<S, usize> = HashMap::from([(initial.clone(), 0)]);
+  let mut current = initial.clone();
+  for i in 1.. {
+    current = step(&current);
+    if let Some(&start) = seen.get(&current) {
+      return Cycle { start, period: i - start };
+    }
+    seen.insert(current.clone(), i);
+  }
+  unreachable!()
+}
+
+/// Finds the cycle with Brent's algorithm, which only ever holds two
+/// states at once at the cost of stepping roughly three times as often.
+/// Never returns if the sequence doesn't repeat.
+pub fn find_cycle_brent<S: Clone + Eq>(initial: &S, mut step: impl FnMut(&S) -> S) -> Cycle {
+  // Find the period by letting the hare run ahead in ever doubling laps
+  let mut power = 1;
+  let mut period = 1;
+  let mut tortoise = initial.clone();
+  let mut hare = step(initial);
+  while tortoise != hare {
+    if power == period {
+      tortoise = hare.clone();
+      power *= 2;
+      period = 0;
+    }
+    hare = step(&hare);
+    period += 1;
+  }
+
+  // With the hare a full period ahead, they first meet where the cycle starts
+  let mut tortoise = initial.clone();
+  let mut hare = (0..period).fold(initial.clone(), |state, _| step(&state));
+  let mut start = 0;
+  while tortoise != hare {
+    tortoise = step(&tortoise);
+    hare = step(&hare);
+    start += 1;
+  }
+
+  Cycle { start, period }
+}
+
+/// The state after `n` steps, skipping ahead once the sequence starts
+/// repeating rather than simulating every step.
+pub fn state_at<S: Clone + Eq + Hash>(initial: &S, mut step: impl FnMut(&S) -> S, n: usize) -> S {
+  let mut seen: HashMap<S, usize> = HashMap::from([(initial.clone(), 0)]);
+  let mut history = vec![initial.clone()];
+  while history.len() <= n {
+    let next = step(&history[history.len() - 1]);
+    if let Some(&start) = seen.get(&next) {
+      let cycle = Cycle {
+        start,
+        period: history.len() - start,
+      };
+      return history.swap_remove(cycle.equivalent_step(n));
+    }
+    seen.insert(next.clone(), history.len());
+    history.push(next);
+  }
+  history.swap_remove(n)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // 3 -> 10 -> 5 -> 16 -> 8 -> 4 -> 2 -> 1 -> 4 -> ...
+  fn collatz(n: &u64) -> u64 {
+    match n % 2 {
+      0 => n / 2,
+      _ => 3 * n + 1,
+    }
+  }
+
+  #[test]
+  fn finders_agree() {
+    let expected = Cycle { start: 5, period: 3 };
+    assert_eq!(find_cycle(&3, collatz), expected);
+    assert_eq!(find_cycle_brent(&3, collatz), expected);
+    assert_eq!(find_cycle(&4, collatz), Cycle { start: 0, period: 3 });
+    assert_eq!(find_cycle_brent(&4, collatz), Cycle { start: 0, period: 3 });
+  }
+
+  #[test]
+  fn jumps_to_far_steps() {
+    let cycle = find_cycle(&3, collatz);
+    assert_eq!(cycle.equivalent_step(2), 2);
+    assert_eq!(cycle.equivalent_step(1_000_000_000), 7);
+
+    assert_eq!(cycle.nth_state(&3, collatz, 4), 8);
+    assert_eq!(cycle.nth_state(&3, collatz, 1_000_000_000), 1);
+    assert_eq!(state_at(&3, collatz, 1_000_000_000), 1);
+    assert_eq!(state_at(&3, collatz, 3), 16);
+  }
+}
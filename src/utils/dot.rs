@@ -0,0 +1,209 @@
+#![allow(dead_code)]
+
+use std::fmt;
+
+/// Node shapes Graphviz knows about that are handy for puzzle graphs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+  Ellipse,
+  Box,
+  Diamond,
+  Circle,
+  DoubleCircle,
+  House,
+  InvHouse,
+  Octagon,
+  Trapezium,
+}
+
+impl Shape {
+  fn as_str(&self) -> &'static str {
+    match self {
+      Shape::Ellipse => "ellipse",
+      Shape::Box => "box",
+      Shape::Diamond => "diamond",
+      Shape::Circle => "circle",
+      Shape::DoubleCircle => "doublecircle",
+      Shape::House => "house",
+      Shape::InvHouse => "invhouse",
+      Shape::Octagon => "octagon",
+      Shape::Trapezium => "trapezium",
+    }
+  }
+}
+
+/// A Graphviz graph built up node by node and edge by edge, then written
+/// out with `to_string()`. Nodes that only appear in edges get Graphviz's
+/// default look.
+///
+/// ```ignore
+/// let mut graph = Graph::digraph("network");
+/// graph.node("a").shape(Shape::Box);
+/// graph.edge("a", "b").label("low");
+/// fs::write("network.dot", graph.to_string())?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Graph {
+  name: String,
+  directed: bool,
+  nodes: Vec<Element>,
+  edges: Vec<(String, String, Element)>,
+}
+
+/// Attributes for one node or edge. Setters chain off [`Graph::node`] and
+/// [`Graph::edge`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Element {
+  id: String,
+  attributes: Vec<(&'static str, String)>,
+}
+
+impl Element {
+  pub fn label(&mut self, label: impl Into<String>) -> &mut Element {
+    self.set("label", label.into())
+  }
+
+  pub fn shape(&mut self, shape: Shape) -> &mut Element {
+    self.set("shape", shape.as_str().to_owned())
+  }
+
+  /// Any colour name or `#rrggbb` Graphviz accepts.
+  pub fn color(&mut self, color: impl Into<String>) -> &mut Element {
+    self.set("color", color.into())
+  }
+
+  /// Sets any other attribute by name, replacing an earlier value.
+  pub fn set(&mut self, key: &'static str, value: String) -> &mut Element {
+    match self.attributes.iter_mut().find(|(k, _)| *k == key) {
+      Some(attribute) => attribute.1 = value,
+      None => self.attributes.push((key, value)),
+    }
+    self
+  }
+
+  fn write_attributes(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    if self.attributes.is_empty() {
+      return Ok(());
+    }
+    let attributes = self
+      .attributes
+      .iter()
+      .map(|(key, value)| format!("{}={}", key, quote(value)))
+      .collect::<Vec<_>>();
+    write!(f, " [{}]", attributes.join(", "))
+  }
+}
+
+impl Graph {
+  pub fn digraph(name: impl Into<String>) -> Graph {
+    Graph {
+      name: name.into(),
+      directed: true,
+      nodes: Vec::new(),
+      edges: Vec::new(),
+    }
+  }
+
+  pub fn undirected(name: impl Into<String>) -> Graph {
+    Graph {
+      directed: false,
+      ..Graph::digraph(name)
+    }
+  }
+
+  /// The node with this id, added if it's new. Nodes are written in the
+  /// order they were first added.
+  pub fn node(&mut self, id: impl Into<String>) -> &mut Element {
+    let id = id.into();
+    let index = match self.nodes.iter().position(|node| node.id == id) {
+      Some(index) => index,
+      None => {
+        self.nodes.push(Element {
+          id,
+          attributes: Vec::new(),
+        });
+        self.nodes.len() - 1
+      }
+    };
+    &mut self.nodes[index]
+  }
+
+  /// Adds an edge. Repeated edges are kept, since puzzle graphs can have
+  /// more than one link between the same pair of nodes.
+  pub fn edge(&mut self, from: impl Into<String>, to: impl Into<String>) -> &mut Element {
+    self.edges.push((from.into(), to.into(), Element::default()));
+    &mut self.edges.last_mut().unwrap().2
+  }
+
+  pub fn node_count(&self) -> usize {
+    self.nodes.len()
+  }
+
+  pub fn edge_count(&self) -> usize {
+    self.edges.len()
+  }
+}
+
+impl fmt::Display for Graph {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let (keyword, arrow) = match self.directed {
+      true => ("digraph", "->"),
+      false => ("graph", "--"),
+    };
+    writeln!(f, "{} {} {{", keyword, quote(&self.name))?;
+    for node in &self.nodes {
+      write!(f, "  {}", quote(&node.id))?;
+      node.write_attributes(f)?;
+      writeln!(f, ";")?;
+    }
+    for (from, to, edge) in &self.edges {
+      write!(f, "  {} {} {}", quote(from), arrow, quote(to))?;
+      edge.write_attributes(f)?;
+      writeln!(f, ";")?;
+    }
+    write!(f, "}}")
+  }
+}
+
+// Helpers
+
+// Ids and values are always quoted so names like `in` or `a<2006` are safe
+fn quote(value: &str) -> String {
+  format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn renders_digraph() {
+    let mut graph = Graph::digraph("modules");
+    graph.node("broadcaster").shape(Shape::House);
+    graph.node("a").shape(Shape::Box).label("%a");
+    graph.node("a").color("red");
+    graph.edge("broadcaster", "a");
+    graph.edge("a", "out\"put").label("x<5");
+
+    assert_eq!(graph.node_count(), 2);
+    assert_eq!(
+      graph.to_string(),
+      [
+        "digraph \"modules\" {",
+        "  \"broadcaster\" [shape=\"house\"];",
+        "  \"a\" [shape=\"box\", label=\"%a\", color=\"red\"];",
+        "  \"broadcaster\" -> \"a\";",
+        "  \"a\" -> \"out\\\"put\" [label=\"x<5\"];",
+        "}",
+      ]
+      .join("\n")
+    );
+  }
+
+  #[test]
+  fn renders_undirected() {
+    let mut graph = Graph::undirected("g");
+    graph.edge("a", "b");
+    assert_eq!(graph.to_string(), "graph \"g\" {\n  \"a\" -- \"b\";\n}");
+  }
+}
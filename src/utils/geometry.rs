@@ -0,0 +1,376 @@
+#![allow(dead_code)]
+
+use std::{
+  fmt,
+  ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+  str::FromStr,
+};
+
+use super::grid::Coord;
+
+/// A 2D integer vector in screen orientation: `x` grows to the right and
+/// `y` grows downwards, so row `y` / column `x` of a parsed map.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec2 {
+  pub x: isize,
+  pub y: isize,
+}
+
+impl Vec2 {
+  pub const ZERO: Vec2 = Vec2 { x: 0, y: 0 };
+
+  pub const fn new(x: isize, y: isize) -> Vec2 {
+    Vec2 { x, y }
+  }
+
+  /// Taxicab distance, i.e. the number of orthogonal steps between the two.
+  pub fn manhattan(self, other: Vec2) -> usize {
+    self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+  }
+
+  /// King's-move distance, where a diagonal step counts as one.
+  pub fn chebyshev(self, other: Vec2) -> usize {
+    self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+  }
+
+  /// Clamps each component to -1, 0 or 1.
+  pub fn signum(self) -> Vec2 {
+    Vec2::new(self.x.signum(), self.y.signum())
+  }
+
+  /// The four orthogonal neighbours in N, E, S, W order.
+  pub fn neighbours4(self) -> impl Iterator<Item = Vec2> {
+    Direction::ALL.into_iter().map(move |dir| self + dir)
+  }
+}
+
+impl Add for Vec2 {
+  type Output = Vec2;
+
+  fn add(self, other: Vec2) -> Vec2 {
+    Vec2::new(self.x + other.x, self.y + other.y)
+  }
+}
+
+impl Add<Direction> for Vec2 {
+  type Output = Vec2;
+
+  fn add(self, dir: Direction) -> Vec2 {
+    self + dir.to_vec()
+  }
+}
+
+impl AddAssign for Vec2 {
+  fn add_assign(&mut self, other: Vec2) {
+    *self = *self + other;
+  }
+}
+
+impl AddAssign<Direction> for Vec2 {
+  fn add_assign(&mut self, dir: Direction) {
+    *self = *self + dir;
+  }
+}
+
+impl Sub for Vec2 {
+  type Output = Vec2;
+
+  fn sub(self, other: Vec2) -> Vec2 {
+    Vec2::new(self.x - other.x, self.y - other.y)
+  }
+}
+
+impl SubAssign for Vec2 {
+  fn sub_assign(&mut self, other: Vec2) {
+    *self = *self - other;
+  }
+}
+
+impl Neg for Vec2 {
+  type Output = Vec2;
+
+  fn neg(self) -> Vec2 {
+    Vec2::new(-self.x, -self.y)
+  }
+}
+
+impl Mul<isize> for Vec2 {
+  type Output = Vec2;
+
+  fn mul(self, factor: isize) -> Vec2 {
+    Vec2::new(self.x * factor, self.y * factor)
+  }
+}
+
+/// Grid positions are (row, col), so `y` comes first.
+impl From<Coord> for Vec2 {
+  fn from((row, col): Coord) -> Vec2 {
+    Vec2::new(col, row)
+  }
+}
+
+impl From<Vec2> for Coord {
+  fn from(v: Vec2) -> Coord {
+    (v.y, v.x)
+  }
+}
+
+impl fmt::Display for Vec2 {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "({}, {})", self.x, self.y)
+  }
+}
+
+/// One of the four compass directions, with north pointing up the screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+  North,
+  East,
+  South,
+  West,
+}
+
+impl Direction {
+  /// Clockwise from north.
+  pub const ALL: [Direction; 4] = [Direction::North, Direction::East, Direction::South, Direction::West];
+
+  pub fn turn_left(self) -> Direction {
+    match self {
+      Direction::North => Direction::West,
+      Direction::East => Direction::North,
+      Direction::South => Direction::East,
+      Direction::West => Direction::South,
+    }
+  }
+
+  pub fn turn_right(self) -> Direction {
+    match self {
+      Direction::North => Direction::East,
+      Direction::East => Direction::South,
+      Direction::South => Direction::West,
+      Direction::West => Direction::North,
+    }
+  }
+
+  pub fn reverse(self) -> Direction {
+    match self {
+      Direction::North => Direction::South,
+      Direction::East => Direction::West,
+      Direction::South => Direction::North,
+      Direction::West => Direction::East,
+    }
+  }
+
+  /// The unit step taken when moving one cell this way.
+  pub fn to_vec(self) -> Vec2 {
+    match self {
+      Direction::North => Vec2::new(0, -1),
+      Direction::East => Vec2::new(1, 0),
+      Direction::South => Vec2::new(0, 1),
+      Direction::West => Vec2::new(-1, 0),
+    }
+  }
+
+  pub fn is_vertical(self) -> bool {
+    matches!(self, Direction::North | Direction::South)
+  }
+}
+
+impl From<Direction> for Vec2 {
+  fn from(dir: Direction) -> Vec2 {
+    dir.to_vec()
+  }
+}
+
+/// Accepts both relative (`U`/`D`/`L`/`R`) and compass (`N`/`S`/`E`/`W`)
+/// letters, in either case.
+impl TryFrom<char> for Direction {
+  type Error = String;
+
+  fn try_from(ch: char) -> Result<Direction, String> {
+    match ch.to_ascii_uppercase() {
+      'U' | 'N' => Ok(Direction::North),
+      'R' | 'E' => Ok(Direction::East),
+      'D' | 'S' => Ok(Direction::South),
+      'L' | 'W' => Ok(Direction::West),
+      _ => Err(format!("Invalid direction '{}'", ch)),
+    }
+  }
+}
+
+impl FromStr for Direction {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Direction, String> {
+    let mut chars = s.trim().chars();
+    match (chars.next(), chars.next()) {
+      (Some(ch), None) => Direction::try_from(ch),
+      _ => Err(format!("Invalid direction '{}'", s)),
+    }
+  }
+}
+
+/// A 3D integer vector. Axes have no fixed orientation, puzzles using
+/// these tend to define their own.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec3 {
+  pub x: isize,
+  pub y: isize,
+  pub z: isize,
+}
+
+impl Vec3 {
+  pub const ZERO: Vec3 = Vec3 { x: 0, y: 0, z: 0 };
+
+  /// Unit steps along each axis, both ways.
+  pub const UNITS: [Vec3; 6] = [
+    Vec3::new(1, 0, 0),
+    Vec3::new(-1, 0, 0),
+    Vec3::new(0, 1, 0),
+    Vec3::new(0, -1, 0),
+    Vec3::new(0, 0, 1),
+    Vec3::new(0, 0, -1),
+  ];
+
+  pub const fn new(x: isize, y: isize, z: isize) -> Vec3 {
+    Vec3 { x, y, z }
+  }
+
+  pub fn manhattan(self, other: Vec3) -> usize {
+    self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+  }
+
+  /// Clamps each component to -1, 0 or 1.
+  pub fn signum(self) -> Vec3 {
+    Vec3::new(self.x.signum(), self.y.signum(), self.z.signum())
+  }
+
+  /// The six face-sharing neighbours.
+  pub fn neighbours6(self) -> impl Iterator<Item = Vec3> {
+    Vec3::UNITS.into_iter().map(move |unit| self + unit)
+  }
+
+  /// Componentwise minimum and maximum, for growing bounding boxes.
+  pub fn min(self, other: Vec3) -> Vec3 {
+    Vec3::new(self.x.min(other.x), self.y.min(other.y), self.z.min(other.z))
+  }
+
+  pub fn max(self, other: Vec3) -> Vec3 {
+    Vec3::new(self.x.max(other.x), self.y.max(other.y), self.z.max(other.z))
+  }
+}
+
+impl Add for Vec3 {
+  type Output = Vec3;
+
+  fn add(self, other: Vec3) -> Vec3 {
+    Vec3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+  }
+}
+
+impl AddAssign for Vec3 {
+  fn add_assign(&mut self, other: Vec3) {
+    *self = *self + other;
+  }
+}
+
+impl Sub for Vec3 {
+  type Output = Vec3;
+
+  fn sub(self, other: Vec3) -> Vec3 {
+    Vec3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+  }
+}
+
+impl SubAssign for Vec3 {
+  fn sub_assign(&mut self, other: Vec3) {
+    *self = *self - other;
+  }
+}
+
+impl Neg for Vec3 {
+  type Output = Vec3;
+
+  fn neg(self) -> Vec3 {
+    Vec3::new(-self.x, -self.y, -self.z)
+  }
+}
+
+impl Mul<isize> for Vec3 {
+  type Output = Vec3;
+
+  fn mul(self, factor: isize) -> Vec3 {
+    Vec3::new(self.x * factor, self.y * factor, self.z * factor)
+  }
+}
+
+impl fmt::Display for Vec3 {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "({}, {}, {})", self.x, self.y, self.z)
+  }
+}
+
+/// Parses `x,y,z`, the way 3D puzzle inputs usually list points.
+impl FromStr for Vec3 {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Vec3, String> {
+    let parts = s
+      .split(',')
+      .map(|n| n.trim().parse::<isize>())
+      .collect::<Result<Vec<_>, _>>()
+      .map_err(|e| format!("Invalid point '{}': {}", s, e))?;
+    match parts[..] {
+      [x, y, z] => Ok(Vec3::new(x, y, z)),
+      _ => Err(format!("Invalid point '{}': expected x,y,z", s)),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn vector_arithmetic_and_distances() {
+    let a = Vec2::new(1, -2);
+    let b = Vec2::new(-3, 4);
+    assert_eq!(a + b, Vec2::new(-2, 2));
+    assert_eq!(a - b, Vec2::new(4, -6));
+    assert_eq!(-a * 3, Vec2::new(-3, 6));
+    assert_eq!(a.manhattan(b), 10);
+    assert_eq!(a.chebyshev(b), 6);
+    assert_eq!((b - a).signum(), Vec2::new(-1, 1));
+  }
+
+  #[test]
+  fn turning_and_parsing() {
+    assert_eq!(Direction::North.turn_right(), Direction::East);
+    assert_eq!(Direction::North.turn_left(), Direction::West);
+    assert_eq!(Direction::East.reverse(), Direction::West);
+    assert!(Direction::ALL.iter().all(|d| d.turn_left().turn_right() == *d));
+    assert_eq!("U".parse(), Ok(Direction::North));
+    assert_eq!("w".parse(), Ok(Direction::West));
+    assert!("UP".parse::<Direction>().is_err());
+    assert_eq!(Vec2::ZERO + Direction::South, Vec2::new(0, 1));
+  }
+
+  #[test]
+  fn converts_to_grid_coords() {
+    let pos = Vec2::new(3, 1);
+    assert_eq!(Coord::from(pos), (1, 3));
+    assert_eq!(Vec2::from((1, 3)), pos);
+  }
+
+  #[test]
+  fn vec3_arithmetic_and_parsing() {
+    let a = Vec3::new(1, -2, 3);
+    let b: Vec3 = "-3, 4,0".parse().unwrap();
+    assert_eq!(a + b, Vec3::new(-2, 2, 3));
+    assert_eq!(-(a - b) * 2, Vec3::new(-8, 12, -6));
+    assert_eq!(a.manhattan(b), 13);
+    assert_eq!(a.min(b), Vec3::new(-3, -2, 0));
+    assert_eq!(Vec3::ZERO.neighbours6().map(|n| n.manhattan(Vec3::ZERO)).sum::<usize>(), 6);
+    assert!("1,2".parse::<Vec3>().is_err());
+  }
+}
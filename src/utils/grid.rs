@@ -0,0 +1,340 @@
+#![allow(dead_code)]
+
+use std::{
+  fmt,
+  ops::{Index, IndexMut},
+};
+
+use super::geometry::Vec2;
+
+/// A (row, col) position. Signed so stepping off the edge of a grid is
+/// just an out-of-bounds lookup rather than an underflow. Lookups also
+/// take a [`Vec2`], which converts with `y` as the row.
+pub type Coord = (isize, isize);
+
+const NEIGHBOURS_4: [Coord; 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+const NEIGHBOURS_8: [Coord; 8] = [
+  (-1, -1),
+  (-1, 0),
+  (-1, 1),
+  (0, -1),
+  (0, 1),
+  (1, -1),
+  (1, 0),
+  (1, 1),
+];
+
+/// A dense, rectangular 2D map stored row-major, e.g. the character maps
+/// most AoC puzzles hand over as input.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+  width: usize,
+  height: usize,
+  cells: Vec<T>,
+}
+
+impl Grid<char> {
+  /// Parses a character map, one line per row. Trailing blank lines (from
+  /// a final newline in the input file) are ignored.
+  pub fn parse(lines: &[&str]) -> Grid<char> {
+    Grid::parse_with(lines, |ch| ch)
+  }
+}
+
+impl<T> Grid<T> {
+  /// Parses a map line by line, converting each character with `f`.
+  pub fn parse_with(lines: &[&str], f: impl Fn(char) -> T) -> Grid<T> {
+    let mut rows = lines.iter().map(|line| line.chars().map(&f).collect::<Vec<T>>()).collect::<Vec<_>>();
+    while rows.last().is_some_and(|row| row.is_empty()) {
+      rows.pop();
+    }
+    Grid::from_rows(rows)
+  }
+
+  /// Builds a grid from rows of cells. Panics if the rows aren't all the same length.
+  pub fn from_rows(rows: Vec<Vec<T>>) -> Grid<T> {
+    let height = rows.len();
+    let width = rows.first().map_or(0, |row| row.len());
+    if let Some(row) = rows.iter().position(|row| row.len() != width) {
+      panic!("Ragged grid: row {} has {} cells, expected {}", row, rows[row].len(), width);
+    }
+
+    Grid {
+      width,
+      height,
+      cells: rows.into_iter().flatten().collect(),
+    }
+  }
+
+  pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+  where
+    T: Clone,
+  {
+    Grid {
+      width,
+      height,
+      cells: vec![fill; width * height],
+    }
+  }
+
+  pub fn width(&self) -> usize {
+    self.width
+  }
+
+  pub fn height(&self) -> usize {
+    self.height
+  }
+
+  pub fn in_bounds(&self, pos: impl Into<Coord>) -> bool {
+    let pos = pos.into();
+    pos.0 >= 0 && pos.1 >= 0 && (pos.0 as usize) < self.height && (pos.1 as usize) < self.width
+  }
+
+  pub fn get(&self, pos: impl Into<Coord>) -> Option<&T> {
+    self.offset(pos).map(|i| &self.cells[i])
+  }
+
+  pub fn get_mut(&mut self, pos: impl Into<Coord>) -> Option<&mut T> {
+    self.offset(pos).map(|i| &mut self.cells[i])
+  }
+
+  /// Overwrites the cell at `pos`, returning false if it's out of bounds.
+  pub fn set(&mut self, pos: impl Into<Coord>, value: T) -> bool {
+    match self.get_mut(pos) {
+      Some(cell) => {
+        *cell = value;
+        true
+      }
+      None => false,
+    }
+  }
+
+  /// Every position in row-major order.
+  pub fn positions(&self) -> impl Iterator<Item = Coord> {
+    let width = self.width;
+    (0..self.height * width).map(move |i| ((i / width) as isize, (i % width) as isize))
+  }
+
+  /// Every position paired with its cell, in row-major order.
+  pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+    self.positions().zip(self.cells.iter())
+  }
+
+  /// The in-bounds orthogonal neighbours of `pos` (N, S, W, E).
+  pub fn neighbours4(&self, pos: Coord) -> impl Iterator<Item = Coord> + '_ {
+    self.offsets_from(pos, &NEIGHBOURS_4)
+  }
+
+  /// The in-bounds orthogonal and diagonal neighbours of `pos`.
+  pub fn neighbours8(&self, pos: Coord) -> impl Iterator<Item = Coord> + '_ {
+    self.offsets_from(pos, &NEIGHBOURS_8)
+  }
+
+  pub fn row(&self, row: usize) -> &[T] {
+    &self.cells[row * self.width..(row + 1) * self.width]
+  }
+
+  pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+    (0..self.height).map(move |row| self.row(row))
+  }
+
+  pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+    assert!(col < self.width, "Column {} out of bounds for width {}", col, self.width);
+    self.cells.iter().skip(col).step_by(self.width)
+  }
+
+  pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+    (0..self.width).map(move |col| self.column(col))
+  }
+
+  /// The first position (row-major) holding `value`.
+  pub fn find(&self, value: &T) -> Option<Coord>
+  where
+    T: PartialEq,
+  {
+    self.iter().find(|(_, cell)| *cell == value).map(|(pos, _)| pos)
+  }
+
+  /// Every position holding `value`, in row-major order.
+  pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Coord> + 'a
+  where
+    T: PartialEq,
+  {
+    self.iter().filter(move |(_, cell)| *cell == value).map(|(pos, _)| pos)
+  }
+
+  pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+    Grid {
+      width: self.width,
+      height: self.height,
+      cells: self.cells.iter().map(f).collect(),
+    }
+  }
+
+  /// Flips the grid over its main diagonal so rows become columns.
+  pub fn transpose(&self) -> Grid<T>
+  where
+    T: Clone,
+  {
+    Grid::from_rows(self.columns().map(|col| col.cloned().collect()).collect())
+  }
+
+  /// A view of this grid repeated forever in every direction, for maps
+  /// that are periodic rather than bounded.
+  pub fn tiled(&self) -> Tiled<'_, T> {
+    Tiled { grid: self }
+  }
+
+  // Helpers
+  fn offset(&self, pos: impl Into<Coord>) -> Option<usize> {
+    let pos = pos.into();
+    match self.in_bounds(pos) {
+      true => Some(pos.0 as usize * self.width + pos.1 as usize),
+      false => None,
+    }
+  }
+
+  fn offsets_from<'a>(&'a self, pos: Coord, deltas: &'a [Coord]) -> impl Iterator<Item = Coord> + 'a {
+    deltas
+      .iter()
+      .map(move |d| (pos.0 + d.0, pos.1 + d.1))
+      .filter(|p| self.in_bounds(*p))
+  }
+}
+
+/// See [`Grid::tiled`]. Every position is in bounds and lands on the
+/// matching cell of the underlying grid.
+#[derive(Debug, Clone, Copy)]
+pub struct Tiled<'a, T> {
+  grid: &'a Grid<T>,
+}
+
+impl<'a, T> Tiled<'a, T> {
+  /// The position in the underlying grid that `pos` maps onto.
+  pub fn wrap(&self, pos: impl Into<Coord>) -> Coord {
+    let (row, col) = pos.into();
+    (row.rem_euclid(self.grid.height as isize), col.rem_euclid(self.grid.width as isize))
+  }
+
+  pub fn get(&self, pos: impl Into<Coord>) -> &'a T {
+    &self.grid[self.wrap(pos)]
+  }
+
+  /// Copies out a `width` x `height` region whose top left corner is at
+  /// `top_left`, which can be anywhere.
+  pub fn window(&self, top_left: impl Into<Coord>, width: usize, height: usize) -> Grid<T>
+  where
+    T: Clone,
+  {
+    let (top, left) = top_left.into();
+    let cells = (0..height as isize)
+      .flat_map(|row| (0..width as isize).map(move |col| (top + row, left + col)))
+      .map(|pos| self.get(pos).clone())
+      .collect();
+    Grid { width, height, cells }
+  }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+  type Output = T;
+
+  fn index(&self, pos: Coord) -> &T {
+    self
+      .get(pos)
+      .unwrap_or_else(|| panic!("Position {:?} out of bounds for {}x{} grid", pos, self.height, self.width))
+  }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+  fn index_mut(&mut self, pos: Coord) -> &mut T {
+    let (height, width) = (self.height, self.width);
+    self
+      .get_mut(pos)
+      .unwrap_or_else(|| panic!("Position {:?} out of bounds for {}x{} grid", pos, height, width))
+  }
+}
+
+impl<T> Index<Vec2> for Grid<T> {
+  type Output = T;
+
+  fn index(&self, pos: Vec2) -> &T {
+    &self[Coord::from(pos)]
+  }
+}
+
+impl<T> IndexMut<Vec2> for Grid<T> {
+  fn index_mut(&mut self, pos: Vec2) -> &mut T {
+    &mut self[Coord::from(pos)]
+  }
+}
+
+/// Renders one line per row with each cell's own `Display`, so a
+/// `Grid<char>` prints back out as the map it was parsed from.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    for (i, row) in self.rows().enumerate() {
+      if i > 0 {
+        writeln!(f)?;
+      }
+      for cell in row {
+        write!(f, "{}", cell)?;
+      }
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sample() -> Grid<char> {
+    Grid::parse(&["#..", ".S.", "..#", ""])
+  }
+
+  #[test]
+  fn parses_and_renders() {
+    let grid = sample();
+    assert_eq!((grid.width(), grid.height()), (3, 3));
+    assert_eq!(grid.to_string(), "#..\n.S.\n..#");
+    assert_eq!(grid.find(&'S'), Some((1, 1)));
+    assert_eq!(grid.find_all(&'#').collect::<Vec<_>>(), vec![(0, 0), (2, 2)]);
+  }
+
+  #[test]
+  fn checked_indexing() {
+    let mut grid = sample();
+    assert_eq!(grid.get((-1, 0)), None);
+    assert_eq!(grid.get((0, 3)), None);
+    assert_eq!(grid[(2, 2)], '#');
+    assert!(grid.set((0, 1), '#'));
+    assert!(!grid.set((3, 0), '#'));
+    assert_eq!(grid.row(0), &['#', '#', '.']);
+    assert_eq!(grid[Vec2::new(2, 1)], '.');
+    assert_eq!(grid.get(Vec2::new(2, 2)), Some(&'#'));
+  }
+
+  #[test]
+  fn neighbours_stay_in_bounds() {
+    let grid = sample();
+    assert_eq!(grid.neighbours4((0, 0)).collect::<Vec<_>>(), vec![(1, 0), (0, 1)]);
+    assert_eq!(grid.neighbours8((0, 0)).count(), 3);
+    assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+  }
+
+  #[test]
+  fn columns_and_transpose() {
+    let grid = Grid::parse(&["ab", "cd", "ef"]);
+    assert_eq!(grid.column(1).collect::<String>(), "bdf");
+    assert_eq!(grid.transpose().to_string(), "ace\nbdf");
+  }
+
+  #[test]
+  fn tiled_view_wraps() {
+    let grid = Grid::parse(&["ab", "cd"]);
+    let tiled = grid.tiled();
+    assert_eq!(tiled.get((-1, -1)), &'d');
+    assert_eq!(tiled.get(Vec2::new(5, 2)), &'b');
+    assert_eq!(tiled.window((1, -1), 3, 2).to_string(), "dcd\nbab");
+  }
+}
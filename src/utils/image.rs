@@ -0,0 +1,247 @@
+#![allow(dead_code)]
+
+use std::{
+  fs, io,
+  path::{Path, PathBuf},
+  sync::OnceLock,
+};
+
+use super::grid::Grid;
+
+/// A 24-bit colour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+  pub const BLACK: Rgb = Rgb(0, 0, 0);
+  pub const WHITE: Rgb = Rgb(255, 255, 255);
+  pub const GREY: Rgb = Rgb(96, 96, 96);
+  pub const DARK: Rgb = Rgb(24, 24, 32);
+  pub const RED: Rgb = Rgb(220, 50, 47);
+  pub const GREEN: Rgb = Rgb(80, 200, 80);
+  pub const BLUE: Rgb = Rgb(38, 139, 210);
+  pub const YELLOW: Rgb = Rgb(240, 200, 40);
+}
+
+/// An RGB bitmap that can be written out as PPM or PNG without pulling in
+/// an image crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+  width: usize,
+  height: usize,
+  pixels: Vec<Rgb>,
+}
+
+impl Image {
+  pub fn new(width: usize, height: usize, background: Rgb) -> Image {
+    Image {
+      width,
+      height,
+      pixels: vec![background; width * height],
+    }
+  }
+
+  /// Draws every cell of `grid` as a `scale` x `scale` square coloured by
+  /// `color`.
+  pub fn from_grid<T>(grid: &Grid<T>, scale: usize, color: impl Fn(&T) -> Rgb) -> Image {
+    let mut image = Image::new(grid.width() * scale, grid.height() * scale, Rgb::BLACK);
+    for ((row, col), value) in grid.iter() {
+      let rgb = color(value);
+      for y in 0..scale {
+        for x in 0..scale {
+          image.set(col as usize * scale + x, row as usize * scale + y, rgb);
+        }
+      }
+    }
+    image
+  }
+
+  pub fn width(&self) -> usize {
+    self.width
+  }
+
+  pub fn height(&self) -> usize {
+    self.height
+  }
+
+  pub fn get(&self, x: usize, y: usize) -> Rgb {
+    self.pixels[y * self.width + x]
+  }
+
+  pub fn set(&mut self, x: usize, y: usize, rgb: Rgb) {
+    self.pixels[y * self.width + x] = rgb;
+  }
+
+  /// Binary PPM (P6). Nearly every image viewer opens these.
+  pub fn to_ppm(&self) -> Vec<u8> {
+    let mut out = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+    for Rgb(r, g, b) in &self.pixels {
+      out.extend([*r, *g, *b]);
+    }
+    out
+  }
+
+  /// 8-bit RGB PNG. Image data goes into uncompressed deflate blocks, so
+  /// files are about the size of a PPM, but it keeps the encoder tiny.
+  pub fn to_png(&self) -> Vec<u8> {
+    // Each scanline starts with its filter type, 0 for none
+    let mut raw = Vec::with_capacity((self.width * 3 + 1) * self.height);
+    for row in self.pixels.chunks(self.width.max(1)).take(self.height) {
+      raw.push(0);
+      for Rgb(r, g, b) in row {
+        raw.extend([*r, *g, *b]);
+      }
+    }
+
+    let mut header = Vec::new();
+    header.extend((self.width as u32).to_be_bytes());
+    header.extend((self.height as u32).to_be_bytes());
+    // Bit depth 8, colour type 2 (RGB), default compression, filter and interlace
+    header.extend([8, 2, 0, 0, 0]);
+
+    let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+    write_chunk(&mut out, b"IHDR", &header);
+    write_chunk(&mut out, b"IDAT", &zlib_stored(&raw));
+    write_chunk(&mut out, b"IEND", &[]);
+    out
+  }
+
+  /// Writes a `.png` or `.ppm` depending on the extension of `path`.
+  pub fn save(&self, path: &Path) -> io::Result<()> {
+    match path.extension().and_then(|e| e.to_str()) {
+      Some("png") => fs::write(path, self.to_png()),
+      Some("ppm") => fs::write(path, self.to_ppm()),
+      _ => Err(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("Expected a .png or .ppm path, got {:?}", path),
+      )),
+    }
+  }
+}
+
+static DEBUG_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Turns on [`debug_dump`] for the rest of the run, writing into `dir`.
+pub fn enable_debug_output(dir: &Path) -> io::Result<()> {
+  fs::create_dir_all(dir)?;
+  let _ = DEBUG_DIR.set(dir.to_path_buf());
+  Ok(())
+}
+
+pub fn debug_enabled() -> bool {
+  DEBUG_DIR.get().is_some()
+}
+
+/// Saves `<name>.png` into the debug image directory if debug output is
+/// on, otherwise does nothing. The image is only built when it's needed,
+/// so this is free to leave in a solution.
+pub fn debug_dump(name: &str, build: impl FnOnce() -> Image) {
+  let Some(dir) = DEBUG_DIR.get() else {
+    return;
+  };
+  let path = dir.join(format!("{}.png", name));
+  match build().save(&path) {
+    Ok(()) => eprintln!("Wrote debug image {:?}", path),
+    Err(e) => eprintln!("Unable to write debug image {:?}: {}", path, e),
+  }
+}
+
+// Helpers
+fn write_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+  out.extend((data.len() as u32).to_be_bytes());
+  let start = out.len();
+  out.extend(kind);
+  out.extend(data);
+  let crc = crc32(&out[start..]);
+  out.extend(crc.to_be_bytes());
+}
+
+// A zlib stream of stored (uncompressed) deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+  const MAX_BLOCK: usize = u16::MAX as usize;
+
+  let mut out = vec![0x78, 0x01];
+  let mut blocks = data.chunks(MAX_BLOCK).peekable();
+  if blocks.peek().is_none() {
+    // Still need one (empty) final block
+    out.extend([1, 0, 0, 0xff, 0xff]);
+  }
+  while let Some(block) = blocks.next() {
+    let is_final = blocks.peek().is_none();
+    let len = block.len() as u16;
+    out.push(is_final as u8);
+    out.extend(len.to_le_bytes());
+    out.extend((!len).to_le_bytes());
+    out.extend(block);
+  }
+  out.extend(adler32(data).to_be_bytes());
+  out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+  let mut crc = !0_u32;
+  for &byte in data {
+    crc ^= byte as u32;
+    for _ in 0..8 {
+      crc = match crc & 1 {
+        1 => (crc >> 1) ^ 0xedb8_8320,
+        _ => crc >> 1,
+      };
+    }
+  }
+  !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+  let (mut a, mut b) = (1_u32, 0_u32);
+  for &byte in data {
+    a = (a + byte as u32) % 65521;
+    b = (b + a) % 65521;
+  }
+  b << 16 | a
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sample() -> Image {
+    let grid = Grid::parse(&["#.", ".#"]);
+    Image::from_grid(&grid, 2, |&c| if c == '#' { Rgb::WHITE } else { Rgb::BLACK })
+  }
+
+  #[test]
+  fn draws_scaled_cells() {
+    let image = sample();
+    assert_eq!((image.width(), image.height()), (4, 4));
+    assert_eq!(image.get(1, 1), Rgb::WHITE);
+    assert_eq!(image.get(2, 1), Rgb::BLACK);
+    assert_eq!(image.get(3, 3), Rgb::WHITE);
+
+    let ppm = image.to_ppm();
+    assert!(ppm.starts_with(b"P6\n4 4\n255\n"));
+    assert_eq!(ppm.len(), 11 + 4 * 4 * 3);
+  }
+
+  #[test]
+  fn png_checksums() {
+    assert_eq!(crc32(b"IEND"), 0xae42_6082);
+    assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+
+    let png = sample().to_png();
+    assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+    assert!(png.ends_with(&[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]));
+    // IHDR: 4x4, 8-bit RGB
+    assert_eq!(&png[16..29], &[0, 0, 0, 4, 0, 0, 0, 4, 8, 2, 0, 0, 0]);
+  }
+
+  #[test]
+  fn stored_blocks_split_large_data() {
+    let data = vec![7_u8; 70_000];
+    let zlib = zlib_stored(&data);
+    // Header, two block headers, data and checksum
+    assert_eq!(zlib.len(), 2 + 5 + 65_535 + 5 + (70_000 - 65_535) + 4);
+    assert_eq!(zlib[2], 0);
+    assert_eq!(zlib[2 + 5 + 65_535], 1);
+  }
+}
@@ -0,0 +1,233 @@
+#![allow(dead_code)]
+
+use std::{
+  fmt::Debug,
+  ops::{Add, Sub},
+};
+
+/// Integer types an [`Interval`] can be built over.
+pub trait Bound: Copy + Ord + Debug + Default + Add<Output = Self> + Sub<Output = Self> {
+  const ONE: Self;
+}
+
+impl Bound for i64 {
+  const ONE: i64 = 1;
+}
+
+impl Bound for u64 {
+  const ONE: u64 = 1;
+}
+
+/// A half-open range of integers `[start, end)`. Anything with
+/// `start >= end` is empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+  pub start: T,
+  pub end: T,
+}
+
+impl<T: Bound> Interval<T> {
+  pub fn new(start: T, end: T) -> Interval<T> {
+    Interval { start, end }
+  }
+
+  /// The `len` values starting at `start`, the way AoC usually describes ranges.
+  pub fn from_len(start: T, len: T) -> Interval<T> {
+    Interval::new(start, start + len)
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.start >= self.end
+  }
+
+  pub fn len(&self) -> T {
+    match self.is_empty() {
+      true => T::default(),
+      false => self.end - self.start,
+    }
+  }
+
+  pub fn contains(&self, value: T) -> bool {
+    self.start <= value && value < self.end
+  }
+
+  pub fn intersect(&self, other: &Interval<T>) -> Option<Interval<T>> {
+    let overlap = Interval::new(self.start.max(other.start), self.end.min(other.end));
+    (!overlap.is_empty()).then_some(overlap)
+  }
+
+  /// Splits into the values below `point` and the values from `point` on.
+  /// Either half may come back empty.
+  pub fn split_at(&self, point: T) -> (Interval<T>, Interval<T>) {
+    let point = point.clamp(self.start, self.end.max(self.start));
+    (Interval::new(self.start, point), Interval::new(point, self.end))
+  }
+
+  /// Moves the interval up by `offset`.
+  pub fn shift(&self, offset: T) -> Interval<T> {
+    Interval::new(self.start + offset, self.end + offset)
+  }
+
+  /// Moves the interval so it begins at `start`, keeping its length.
+  pub fn shift_to(&self, start: T) -> Interval<T> {
+    Interval::from_len(start, self.len())
+  }
+}
+
+/// A set of integers stored as sorted, non-overlapping, non-adjacent
+/// intervals, so each operation stays proportional to the number of
+/// ranges rather than the number of values.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+  intervals: Vec<Interval<T>>,
+}
+
+impl<T: Bound> IntervalSet<T> {
+  pub fn new() -> IntervalSet<T> {
+    IntervalSet { intervals: Vec::new() }
+  }
+
+  pub fn insert(&mut self, interval: Interval<T>) {
+    if interval.is_empty() {
+      return;
+    }
+
+    // Swallow every interval that overlaps or touches the new one
+    let first = self.intervals.partition_point(|i| i.end < interval.start);
+    let last = self.intervals.partition_point(|i| i.start <= interval.end);
+    let merged = self.intervals[first..last]
+      .iter()
+      .fold(interval, |acc, i| Interval::new(acc.start.min(i.start), acc.end.max(i.end)));
+    self.intervals.splice(first..last, [merged]);
+  }
+
+  pub fn intervals(&self) -> &[Interval<T>] {
+    &self.intervals
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.intervals.is_empty()
+  }
+
+  /// Total number of values in the set.
+  pub fn len(&self) -> T {
+    self.intervals.iter().fold(T::default(), |acc, i| acc + i.len())
+  }
+
+  pub fn contains(&self, value: T) -> bool {
+    let candidate = self.intervals.partition_point(|i| i.end <= value);
+    self.intervals.get(candidate).is_some_and(|i| i.contains(value))
+  }
+
+  pub fn min(&self) -> Option<T> {
+    self.intervals.first().map(|i| i.start)
+  }
+
+  pub fn max(&self) -> Option<T> {
+    self.intervals.last().map(|i| i.end - T::ONE)
+  }
+
+  pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+    let mut set = self.clone();
+    other.intervals.iter().for_each(|i| set.insert(*i));
+    set
+  }
+
+  pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+    self
+      .intervals
+      .iter()
+      .flat_map(|a| other.intervals.iter().filter_map(|b| a.intersect(b)))
+      .collect()
+  }
+
+  pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+    let mut remaining = self.intervals.clone();
+    for cut in &other.intervals {
+      remaining = remaining
+        .into_iter()
+        .flat_map(|i| {
+          let (below, rest) = i.split_at(cut.start);
+          let (_, above) = rest.split_at(cut.end);
+          [below, above]
+        })
+        .filter(|i| !i.is_empty())
+        .collect();
+    }
+    remaining.into_iter().collect()
+  }
+
+  /// Splits into the values below `point` and the values from `point` on.
+  pub fn split_at(&self, point: T) -> (IntervalSet<T>, IntervalSet<T>) {
+    let (below, above): (Vec<_>, Vec<_>) = self.intervals.iter().map(|i| i.split_at(point)).unzip();
+    (below.into_iter().collect(), above.into_iter().collect())
+  }
+
+  pub fn shift(&self, offset: T) -> IntervalSet<T> {
+    self.intervals.iter().map(|i| i.shift(offset)).collect()
+  }
+}
+
+impl<T: Bound> Default for IntervalSet<T> {
+  fn default() -> IntervalSet<T> {
+    IntervalSet::new()
+  }
+}
+
+impl<T: Bound> FromIterator<Interval<T>> for IntervalSet<T> {
+  fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> IntervalSet<T> {
+    let mut set = IntervalSet::new();
+    iter.into_iter().for_each(|i| set.insert(i));
+    set
+  }
+}
+
+impl<T: Bound> From<Interval<T>> for IntervalSet<T> {
+  fn from(interval: Interval<T>) -> IntervalSet<T> {
+    IntervalSet::from_iter([interval])
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn interval_basics() {
+    let range = Interval::from_len(98_i64, 2);
+    assert_eq!(range, Interval::new(98, 100));
+    assert!(range.contains(99));
+    assert!(!range.contains(100));
+    assert_eq!(range.intersect(&Interval::new(99, 200)), Some(Interval::new(99, 100)));
+    assert_eq!(range.intersect(&Interval::new(100, 200)), None);
+    assert_eq!(range.shift(-48), Interval::new(50, 52));
+    assert_eq!(range.split_at(99), (Interval::new(98, 99), Interval::new(99, 100)));
+    assert!(range.split_at(0).0.is_empty());
+    assert_eq!(Interval::new(5_u64, 3).len(), 0);
+  }
+
+  #[test]
+  fn set_merges_and_measures() {
+    let set: IntervalSet<u64> = [Interval::new(10, 20), Interval::new(0, 5), Interval::new(5, 8), Interval::new(15, 30)]
+      .into_iter()
+      .collect();
+    assert_eq!(set.intervals(), &[Interval::new(0, 8), Interval::new(10, 30)]);
+    assert_eq!(set.len(), 28);
+    assert!(set.contains(7) && !set.contains(8) && set.contains(29));
+    assert_eq!((set.min(), set.max()), (Some(0), Some(29)));
+  }
+
+  #[test]
+  fn set_operations() {
+    let a: IntervalSet<i64> = [Interval::new(0, 10), Interval::new(20, 30)].into_iter().collect();
+    let b: IntervalSet<i64> = Interval::new(5, 25).into();
+
+    assert_eq!(a.union(&b).intervals(), &[Interval::new(0, 30)]);
+    assert_eq!(a.intersection(&b).intervals(), &[Interval::new(5, 10), Interval::new(20, 25)]);
+    assert_eq!(a.difference(&b).intervals(), &[Interval::new(0, 5), Interval::new(25, 30)]);
+
+    let (below, above) = a.split_at(22);
+    assert_eq!(below.len() + above.len(), a.len());
+    assert_eq!(above.shift(-20).intervals(), &[Interval::new(2, 10)]);
+  }
+}
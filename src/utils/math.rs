@@ -0,0 +1,212 @@
+#![allow(dead_code)]
+
+use std::ops::{Div, Rem};
+
+/// Integer types the gcd/lcm helpers work over.
+pub trait Integer: Copy + PartialEq + PartialOrd + Div<Output = Self> + Rem<Output = Self> {
+  const ZERO: Self;
+  fn abs(self) -> Self;
+  fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+  ($($t:ty => $abs:expr),*) => {
+    $(impl Integer for $t {
+      const ZERO: $t = 0;
+      fn abs(self) -> $t {
+        $abs(self)
+      }
+      fn checked_mul(self, other: $t) -> Option<$t> {
+        <$t>::checked_mul(self, other)
+      }
+    })*
+  };
+}
+
+impl_integer!(
+  u32 => |n| n,
+  u64 => |n| n,
+  usize => |n| n,
+  i64 => i64::abs,
+  i128 => i128::abs
+);
+
+/// Greatest common divisor, always non-negative. `gcd(0, 0)` is 0.
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+  let (mut a, mut b) = (a.abs(), b.abs());
+  while b != T::ZERO {
+    (a, b) = (b, a % b);
+  }
+  a
+}
+
+/// Least common multiple. Panics if it doesn't fit in `T`.
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+  checked_lcm(a, b).expect("lcm overflowed")
+}
+
+/// Least common multiple, or `None` if it doesn't fit in `T`.
+pub fn checked_lcm<T: Integer>(a: T, b: T) -> Option<T> {
+  if a == T::ZERO || b == T::ZERO {
+    return Some(T::ZERO);
+  }
+  (a.abs() / gcd(a, b)).checked_mul(b.abs())
+}
+
+pub fn gcd_of<T: Integer>(values: impl IntoIterator<Item = T>) -> T {
+  values.into_iter().fold(T::ZERO, gcd)
+}
+
+/// Least common multiple of every value. Panics on overflow or if `values` is empty.
+pub fn lcm_of<T: Integer>(values: impl IntoIterator<Item = T>) -> T {
+  checked_lcm_of(values).expect("lcm of no values, or lcm overflowed")
+}
+
+/// Least common multiple of every value, or `None` on overflow or if
+/// `values` is empty.
+pub fn checked_lcm_of<T: Integer>(values: impl IntoIterator<Item = T>) -> Option<T> {
+  let mut values = values.into_iter();
+  let first = values.next()?;
+  values.try_fold(first, checked_lcm)
+}
+
+/// Extended Euclid: returns `(g, x, y)` with `a*x + b*y = g = gcd(a, b)`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+  let (mut old_r, mut r) = (a, b);
+  let (mut old_x, mut x) = (1, 0);
+  let (mut old_y, mut y) = (0, 1);
+  while r != 0 {
+    let q = old_r / r;
+    (old_r, r) = (r, old_r - q * r);
+    (old_x, x) = (x, old_x - q * x);
+    (old_y, y) = (y, old_y - q * y);
+  }
+  match old_r < 0 {
+    true => (-old_r, -old_x, -old_y),
+    false => (old_r, old_x, old_y),
+  }
+}
+
+/// The `x` in `[0, m)` with `a*x ≡ 1 (mod m)`, if `a` and `m` are coprime.
+/// `None` if `m` isn't positive.
+pub fn mod_inverse(a: i128, m: i128) -> Option<i128> {
+  if m <= 0 {
+    return None;
+  }
+  let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+  (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// [`mod_inverse`] for unsigned puzzle values.
+pub fn mod_inverse_u64(a: u64, m: u64) -> Option<u64> {
+  mod_inverse(a as i128, m as i128).map(|x| x as u64)
+}
+
+/// `base^exp mod m` by repeated squaring. Intermediates are widened to
+/// u128 so this can't overflow for any u64 inputs. Panics if `m` is 0.
+pub fn mod_pow(base: u64, exp: u64, m: u64) -> u64 {
+  assert!(m != 0, "mod_pow with a modulus of 0");
+  if m == 1 {
+    return 0;
+  }
+  let m = m as u128;
+  let (mut result, mut base, mut exp) = (1_u128, base as u128 % m, exp);
+  while exp > 0 {
+    if exp & 1 == 1 {
+      result = result * base % m;
+    }
+    base = base * base % m;
+    exp >>= 1;
+  }
+  result as u64
+}
+
+/// [`mod_pow`] for signed values, giving a result in `[0, m)`. `None` if
+/// `m` isn't positive or squaring overflows i128, which can only happen
+/// once `m` is past 2^63.
+pub fn mod_pow_i128(base: i128, exp: u64, m: i128) -> Option<i128> {
+  if m <= 0 {
+    return None;
+  }
+  let (mut result, mut base, mut exp) = (1 % m, base.rem_euclid(m), exp);
+  while exp > 0 {
+    if exp & 1 == 1 {
+      result = result.checked_mul(base)? % m;
+    }
+    base = base.checked_mul(base)? % m;
+    exp >>= 1;
+  }
+  Some(result)
+}
+
+/// Chinese Remainder Theorem over `(residue, modulus)` pairs. Moduli
+/// don't need to be coprime. Returns the smallest non-negative `x` that
+/// satisfies every congruence along with the combined modulus, or `None`
+/// if the congruences contradict each other or the modulus overflows.
+pub fn crt(congruences: &[(i128, i128)]) -> Option<(i128, i128)> {
+  congruences.iter().try_fold((0, 1), |(x, m), &(r, n)| {
+    let (g, p, _) = extended_gcd(m, n);
+    let diff = r - x;
+    if diff % g != 0 {
+      return None;
+    }
+
+    // x + m*k ≡ r (mod n)  =>  k ≡ (diff / g) * p (mod n / g)
+    let step = n / g;
+    let k = ((diff / g) % step).checked_mul(p % step)?.rem_euclid(step);
+    let combined = m.checked_mul(step)?;
+    Some((m.checked_mul(k)?.checked_add(x)?.rem_euclid(combined), combined))
+  })
+}
+
+/// [`crt`] for unsigned puzzle values.
+pub fn crt_u64(congruences: &[(u64, u64)]) -> Option<(u64, u64)> {
+  let wide = congruences.iter().map(|&(r, n)| (r as i128, n as i128)).collect::<Vec<_>>();
+  let (x, m) = crt(&wide)?;
+  Some((u64::try_from(x).ok()?, u64::try_from(m).ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn gcd_and_lcm() {
+    assert_eq!(gcd(48_u64, 18), 6);
+    assert_eq!(gcd(-48_i64, 18), 6);
+    assert_eq!(lcm(4_u64, 6), 12);
+    assert_eq!(lcm_of([2_u64, 3, 4, 5]), 60);
+    assert_eq!(gcd_of([12_i128, 18, 30]), 6);
+    assert_eq!(checked_lcm(u64::MAX, u64::MAX - 1), None);
+    assert_eq!(checked_lcm_of(Vec::<u64>::new()), None);
+  }
+
+  #[test]
+  fn modular_arithmetic() {
+    let (g, x, y) = extended_gcd(240, 46);
+    assert_eq!(g, 2);
+    assert_eq!(240 * x + 46 * y, 2);
+    assert_eq!(mod_inverse(3, 11), Some(4));
+    assert_eq!(mod_inverse(-3, 11), Some(7));
+    assert_eq!(mod_inverse(6, 9), None);
+    assert_eq!(mod_inverse(3, 0), None);
+    assert_eq!(mod_inverse_u64(3, 11), Some(4));
+    assert_eq!(mod_inverse_u64(u64::MAX, u64::MAX - 1), Some(1));
+    assert_eq!(mod_pow(4, 13, 497), 445);
+    assert_eq!(mod_pow(u64::MAX, u64::MAX, u64::MAX - 1), 1);
+    assert_eq!(mod_pow_i128(-4, 13, 497), Some(497 - 445));
+    assert_eq!(mod_pow_i128(7, 0, 1), Some(0));
+    assert_eq!(mod_pow_i128(2, 10, 0), None);
+    assert_eq!(mod_pow_i128(3, 5, i128::MAX), Some(243));
+    assert_eq!(mod_pow_i128(i128::MAX - 1, 2, i128::MAX), None);
+  }
+
+  #[test]
+  fn chinese_remainders() {
+    assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+    // Non-coprime moduli, consistent and not
+    assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+    assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+    assert_eq!(crt_u64(&[(0, 7), (12, 13), (55, 59), (25, 31), (12, 19)]), Some((1068781, 3162341)));
+  }
+}
@@ -0,0 +1,105 @@
+#![allow(dead_code)]
+
+use std::{collections::HashMap, hash::Hash};
+
+/// A cache for a recursive function, owned by whoever's solving so it's
+/// dropped along with them instead of living for the whole process.
+///
+/// The function is handed a `recurse` callback to use in place of calling
+/// itself, which looks up (or fills in) the cache for the sub-problem:
+///
+/// ```ignore
+/// let mut memo = Memo::new();
+/// let fib = memo.solve(80, |recurse, n: u64| match n {
+///   0 | 1 => n,
+///   _ => recurse(n - 1) + recurse(n - 2),
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+  cache: HashMap<K, V>,
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> Memo<K, V> {
+  pub fn new() -> Memo<K, V> {
+    Memo { cache: HashMap::new() }
+  }
+
+  /// Evaluates `f` at `key`, reusing anything already worked out by
+  /// earlier calls on this memo. `f` has to give the same answer for the
+  /// same key every time it's used with this memo.
+  pub fn solve(&mut self, key: K, f: impl Fn(&mut dyn FnMut(K) -> V, K) -> V) -> V {
+    evaluate(&mut self.cache, &f, key)
+  }
+
+  pub fn get(&self, key: &K) -> Option<&V> {
+    self.cache.get(key)
+  }
+
+  /// Number of sub-problems cached so far.
+  pub fn len(&self) -> usize {
+    self.cache.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.cache.is_empty()
+  }
+
+  pub fn clear(&mut self) {
+    self.cache.clear();
+  }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> Default for Memo<K, V> {
+  fn default() -> Memo<K, V> {
+    Memo::new()
+  }
+}
+
+/// One-off [`Memo::solve`] with a fresh cache that's thrown away after.
+pub fn memoize<K: Eq + Hash + Clone, V: Clone>(key: K, f: impl Fn(&mut dyn FnMut(K) -> V, K) -> V) -> V {
+  Memo::new().solve(key, f)
+}
+
+// Helpers
+#[allow(clippy::type_complexity)]
+fn evaluate<K: Eq + Hash + Clone, V: Clone>(
+  cache: &mut HashMap<K, V>,
+  f: &dyn Fn(&mut dyn FnMut(K) -> V, K) -> V,
+  key: K,
+) -> V {
+  if let Some(value) = cache.get(&key) {
+    return value.clone();
+  }
+  let value = f(&mut |sub_key| evaluate(cache, f, sub_key), key.clone());
+  cache.insert(key, value.clone());
+  value
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn recursion_is_cached() {
+    let mut memo = Memo::new();
+    let fib = memo.solve(90_u64, |recurse, n| match n {
+      0 | 1 => n,
+      _ => recurse(n - 1) + recurse(n - 2),
+    });
+    assert_eq!(fib, 2880067194370816120);
+    assert_eq!(memo.len(), 91);
+    assert_eq!(memo.get(&10), Some(&55));
+  }
+
+  #[test]
+  fn closures_can_capture_input() {
+    // Ways to climb `n` stairs taking any of the allowed step sizes
+    let steps = [1, 3, 5];
+    let ways = memoize(10_usize, |recurse, n| match n {
+      0 => 1_u64,
+      _ => steps.iter().filter(|&&s| s <= n).map(|&s| recurse(n - s)).sum(),
+    });
+    assert_eq!(ways, 47);
+  }
+}
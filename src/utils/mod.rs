@@ -0,0 +1,19 @@
+pub mod animation;
+pub mod bitgrid;
+pub mod cpu;
+pub mod cycle;
+pub mod dot;
+pub mod geometry;
+pub mod grid;
+pub mod image;
+pub mod interval;
+pub mod math;
+pub mod memo;
+pub mod ocr;
+pub mod parse;
+pub mod polygon;
+pub mod polynomial;
+pub mod search;
+pub mod snapshot;
+pub mod sparse_grid;
+pub mod voxel;
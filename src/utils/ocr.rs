@@ -0,0 +1,140 @@
+#![allow(dead_code)]
+
+use itertools::Itertools;
+
+/// The 4x6 block-letter font used by most AoC ASCII-art answers. Glyphs are
+/// 4 columns wide (except I and Y) with one blank column between letters.
+const FONT_6: [(char, &str); 18] = [
+  ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+  ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+  ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+  ('E', "####\n#...\n###.\n#...\n#...\n####"),
+  ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+  ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+  ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+  ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+  ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+  ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+  ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+  ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+  ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+  ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+  ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+  ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+  ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+  ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+/// The larger 6x10 block-letter font, letters separated by two blank columns.
+const FONT_10: [(char, &str); 15] = [
+  ('A', "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#"),
+  ('B', "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####."),
+  ('C', ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####."),
+  ('E', "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######"),
+  ('F', "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#....."),
+  ('G', ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#"),
+  ('H', "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#"),
+  ('J', "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###.."),
+  ('K', "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#"),
+  ('L', "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######"),
+  ('N', "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#"),
+  ('P', "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#....."),
+  ('R', "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#"),
+  ('X', "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#"),
+  ('Z', "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######"),
+];
+
+/// Reads the block letters out of a lit/unlit pixel grid, picking the
+/// font by the grid's height (6 or 10 rows). Letters are split on fully
+/// blank columns, so any amount of spacing between them is fine.
+///
+/// Returns `None` if the grid isn't a known font height or any glyph
+/// doesn't match a known letter.
+pub fn recognize(pixels: &[Vec<bool>]) -> Option<String> {
+  let font: &[(char, &str)] = match pixels.len() {
+    6 => &FONT_6,
+    10 => &FONT_10,
+    _ => return None,
+  };
+
+  let width = pixels.iter().map(|row| row.len()).max().unwrap_or(0);
+  let lit = |row: usize, col: usize| pixels[row].get(col).copied().unwrap_or(false);
+  let blank_column = |col: usize| (0..pixels.len()).all(|row| !lit(row, col));
+
+  let mut letters = String::new();
+  let mut col = 0;
+  while col < width {
+    if blank_column(col) {
+      col += 1;
+      continue;
+    }
+
+    let start = col;
+    while col < width && !blank_column(col) {
+      col += 1;
+    }
+
+    let glyph = (0..pixels.len())
+      .map(|row| {
+        (start..col)
+          .map(|c| if lit(row, c) { '#' } else { '.' })
+          .collect::<String>()
+      })
+      .join("\n");
+
+    let (letter, _) = font.iter().find(|(_, shape)| *shape == glyph)?;
+    letters.push(*letter);
+  }
+
+  Some(letters)
+}
+
+/// Same as `recognize` but for a display drawn with `#` for lit pixels
+/// and anything else (usually `.` or a space) for unlit ones.
+pub fn recognize_str(display: &str) -> Option<String> {
+  let pixels = display
+    .lines()
+    .filter(|line| !line.trim().is_empty())
+    .map(|line| line.chars().map(|c| c == '#').collect_vec())
+    .collect_vec();
+  recognize(&pixels)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn reads_small_font() {
+    let display = "\
+#..#.####.#....#.....##.
+#..#.#....#....#....#..#
+####.###..#....#....#..#
+#..#.#....#....#....#..#
+#..#.#....#....#....#..#
+#..#.####.####.####..##.";
+    assert_eq!(recognize_str(display), Some("HELLO".to_owned()));
+  }
+
+  #[test]
+  fn reads_large_font() {
+    let display = "\
+#....#..#....#
+##...#..#....#
+##...#...#..#.
+#.#..#...#..#.
+#.#..#....##..
+#..#.#....##..
+#..#.#...#..#.
+#...##...#..#.
+#...##..#....#
+#....#..#....#";
+    assert_eq!(recognize_str(display), Some("NX".to_owned()));
+  }
+
+  #[test]
+  fn unknown_glyph_is_none() {
+    let display = "#\n#\n#\n#\n#\n#";
+    assert_eq!(recognize_str(display), None);
+  }
+}
@@ -0,0 +1,214 @@
+#![allow(dead_code)]
+
+use std::{collections::HashMap, fmt, str::FromStr};
+
+/// Something in the input didn't look the way a solution expected.
+/// `line` is 1-based so it matches what an editor shows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+  pub line: usize,
+  pub message: String,
+}
+
+impl ParseError {
+  pub fn new(line: usize, message: impl Into<String>) -> ParseError {
+    ParseError {
+      line,
+      message: message.into(),
+    }
+  }
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "line {}: {}", self.line, self.message)
+  }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Every run of digits in `s`, ignoring anything else. Dashes are treated
+/// as separators, so `"2-4"` gives `[2, 4]`. Panics if a number doesn't
+/// fit in `T`.
+pub fn unsigned_ints<T: FromStr>(s: &str) -> Vec<T> {
+  extract_ints(s, false)
+}
+
+/// Like [`unsigned_ints`] but a `-` directly before a number makes it
+/// negative, so `"x=-5, y=3"` gives `[-5, 3]`.
+pub fn signed_ints<T: FromStr>(s: &str) -> Vec<T> {
+  extract_ints(s, true)
+}
+
+/// A run of non-blank lines from the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Block<'a> {
+  /// Line number of the block's first line.
+  pub line: usize,
+  pub lines: &'a [&'a str],
+}
+
+/// Groups lines into blocks separated by one or more blank lines, the way
+/// AoC lays out multi-line records.
+pub fn blocks<'a>(lines: &'a [&'a str]) -> Vec<Block<'a>> {
+  let mut blocks = Vec::new();
+  let mut start: Option<usize> = None;
+  for (i, line) in lines.iter().enumerate() {
+    match (line.trim().is_empty(), start) {
+      (true, Some(first)) => {
+        blocks.push(Block {
+          line: first + 1,
+          lines: &lines[first..i],
+        });
+        start = None;
+      }
+      (false, None) => start = Some(i),
+      _ => {}
+    }
+  }
+  if let Some(first) = start {
+    blocks.push(Block {
+      line: first + 1,
+      lines: &lines[first..],
+    });
+  }
+  blocks
+}
+
+/// Reads whitespace-separated `key:value` pairs from every line of a
+/// block, e.g. `ecl:gry pid:860033327`.
+pub fn record<'a>(block: &Block<'a>) -> Result<HashMap<&'a str, &'a str>, ParseError> {
+  let mut fields = HashMap::new();
+  for (offset, line) in block.lines.iter().enumerate() {
+    let line_number = block.line + offset;
+    for token in line.split_whitespace() {
+      let (key, value) = token
+        .split_once(':')
+        .ok_or_else(|| ParseError::new(line_number, format!("expected key:value, found '{}'", token)))?;
+      if fields.insert(key, value).is_some() {
+        return Err(ParseError::new(line_number, format!("duplicate key '{}'", key)));
+      }
+    }
+  }
+  Ok(fields)
+}
+
+/// Matches `text` against a pattern where each `{}` captures the text up
+/// to the next literal part, e.g. `"{} = ({}, {})"` against
+/// `"AAA = (BBB, CCC)"` gives `["AAA", "BBB", "CCC"]`. Returns `None` if
+/// the literal parts don't line up. A pattern with no `{}` only matches
+/// itself, capturing nothing.
+pub fn scan<'a>(pattern: &str, text: &'a str) -> Option<Vec<&'a str>> {
+  let literals: Vec<&str> = pattern.split("{}").collect();
+  if literals.len() < 2 {
+    return (text == pattern).then(Vec::new);
+  }
+  assert!(
+    literals[1..literals.len() - 1].iter().all(|l| !l.is_empty()),
+    "Ambiguous scan pattern '{}': placeholders need text between them",
+    pattern
+  );
+
+  let mut rest = text.strip_prefix(literals[0])?;
+  let mut captures = Vec::with_capacity(literals.len() - 1);
+  for (i, literal) in literals.iter().enumerate().skip(1) {
+    let capture = match i == literals.len() - 1 {
+      true => rest.strip_suffix(literal)?,
+      false => &rest[..rest.find(literal)?],
+    };
+    captures.push(capture);
+    rest = &rest[capture.len() + literal.len()..];
+  }
+  Some(captures)
+}
+
+/// Runs [`scan`] over every non-blank line, reporting the first line that
+/// doesn't match.
+pub fn scan_lines<'a>(pattern: &str, lines: &[&'a str]) -> Result<Vec<Vec<&'a str>>, ParseError> {
+  lines
+    .iter()
+    .enumerate()
+    .filter(|(_, line)| !line.trim().is_empty())
+    .map(|(i, line)| {
+      scan(pattern, line).ok_or_else(|| ParseError::new(i + 1, format!("'{}' doesn't match '{}'", line, pattern)))
+    })
+    .collect()
+}
+
+/// Parses one captured value, tagging any failure with its line.
+pub fn field<T>(value: &str, line: usize) -> Result<T, ParseError>
+where
+  T: FromStr,
+  T::Err: fmt::Display,
+{
+  value
+    .trim()
+    .parse()
+    .map_err(|e| ParseError::new(line, format!("invalid value '{}': {}", value, e)))
+}
+
+// Helpers
+fn extract_ints<T: FromStr>(s: &str, signed: bool) -> Vec<T> {
+  let bytes = s.as_bytes();
+  let mut nums = Vec::new();
+  let mut i = 0;
+  while i < bytes.len() {
+    if !bytes[i].is_ascii_digit() {
+      i += 1;
+      continue;
+    }
+
+    let negative = signed && i > 0 && bytes[i - 1] == b'-';
+    let start = if negative { i - 1 } else { i };
+    while i < bytes.len() && bytes[i].is_ascii_digit() {
+      i += 1;
+    }
+    let digits = &s[start..i];
+    match digits.parse() {
+      Ok(n) => nums.push(n),
+      Err(_) => panic!("Number {} doesn't fit in {}", digits, std::any::type_name::<T>()),
+    }
+  }
+  nums
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn extracts_numbers() {
+    assert_eq!(unsigned_ints::<u32>("Card 1: 41 48 | 83  6"), vec![1, 41, 48, 83, 6]);
+    assert_eq!(unsigned_ints::<u8>("2-4,6-8"), vec![2, 4, 6, 8]);
+    assert_eq!(signed_ints::<i64>("x=-5, y=3..-12"), vec![-5, 3, -12]);
+    assert!(unsigned_ints::<u64>("no numbers").is_empty());
+  }
+
+  #[test]
+  fn groups_blocks_and_records() {
+    let lines = ["ecl:gry pid:860", "byr:1937", "", "", "iyr:2013 ecl", ""];
+    let blocks = blocks(&lines);
+    assert_eq!(blocks.len(), 2);
+    assert_eq!((blocks[0].line, blocks[0].lines.len()), (1, 2));
+    assert_eq!(blocks[1].line, 5);
+
+    let passport = record(&blocks[0]).unwrap();
+    assert_eq!(passport.get("byr"), Some(&"1937"));
+    assert_eq!(passport.len(), 3);
+    assert_eq!(record(&blocks[1]), Err(ParseError::new(5, "expected key:value, found 'ecl'")));
+  }
+
+  #[test]
+  fn scans_patterns() {
+    assert_eq!(scan("{} = ({}, {})", "AAA = (BBB, CCC)"), Some(vec!["AAA", "BBB", "CCC"]));
+    assert_eq!(scan("move {} from {} to {}", "move 1 from 2 to 13"), Some(vec!["1", "2", "13"]));
+    assert_eq!(scan("{} = ({}, {})", "AAA = BBB"), None);
+    assert_eq!(scan("noop", "noop"), Some(vec![]));
+    assert_eq!(scan("noop", "noop 3"), None);
+
+    let err = scan_lines("{} -> {}", &["a -> b", "c => d"]).unwrap_err();
+    assert_eq!(err.line, 2);
+    assert_eq!(field::<u32>("x", 7).unwrap_err().line, 7);
+    assert_eq!(field::<u32>(" 42", 1), Ok(42));
+  }
+}
@@ -0,0 +1,171 @@
+#![allow(dead_code)]
+
+use super::{
+  geometry::{Direction, Vec2},
+  math,
+};
+
+/// A simple (non self-intersecting) polygon on the integer lattice, stored
+/// as its corners in order. The last corner joins back up to the first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon {
+  vertices: Vec<Vec2>,
+}
+
+impl Polygon {
+  pub fn new(vertices: Vec<Vec2>) -> Polygon {
+    Polygon { vertices }
+  }
+
+  /// Walks `(direction, length)` steps from `start`, dropping a corner
+  /// after each one. A walk that ends back on `start` doesn't repeat it.
+  pub fn from_steps(start: Vec2, steps: impl IntoIterator<Item = (Direction, isize)>) -> Polygon {
+    let mut vertices = vec![start];
+    let mut current = start;
+    for (dir, length) in steps {
+      current += dir.to_vec() * length;
+      vertices.push(current);
+    }
+    if vertices.len() > 1 && vertices.last() == Some(&start) {
+      vertices.pop();
+    }
+    Polygon::new(vertices)
+  }
+
+  pub fn vertices(&self) -> &[Vec2] {
+    &self.vertices
+  }
+
+  /// Twice the enclosed area by the shoelace formula. Lattice polygons can
+  /// have half-unit areas, so doubling keeps this exact.
+  pub fn double_area(&self) -> usize {
+    self
+      .edges()
+      .map(|(a, b)| a.x * b.y - b.x * a.y)
+      .sum::<isize>()
+      .unsigned_abs()
+  }
+
+  pub fn area(&self) -> f64 {
+    self.double_area() as f64 / 2.0
+  }
+
+  /// Number of lattice points on the edges, corners included.
+  pub fn boundary_points(&self) -> usize {
+    self
+      .edges()
+      .map(|(a, b)| math::gcd(a.x.abs_diff(b.x), a.y.abs_diff(b.y)))
+      .sum()
+  }
+
+  /// Number of lattice points strictly inside, by Pick's theorem
+  /// `A = i + b/2 - 1`. Degenerate polygons, like a walk that doubles back
+  /// on itself, have no inside, so this is 0 for them.
+  pub fn interior_points(&self) -> usize {
+    (self.double_area() + 2).saturating_sub(self.boundary_points()) / 2
+  }
+
+  /// Every lattice point inside or on the polygon, i.e. the number of grid
+  /// cells covered when the vertices are cell centres.
+  pub fn lattice_points(&self) -> usize {
+    self.interior_points() + self.boundary_points()
+  }
+
+  pub fn on_boundary(&self, point: Vec2) -> bool {
+    self.edges().any(|(a, b)| {
+      let (ab, ap) = (b - a, point - a);
+      ab.x * ap.y == ab.y * ap.x
+        && point.x >= a.x.min(b.x)
+        && point.x <= a.x.max(b.x)
+        && point.y >= a.y.min(b.y)
+        && point.y <= a.y.max(b.y)
+    })
+  }
+
+  /// Whether `point` is inside the polygon or on its boundary.
+  pub fn contains(&self, point: Vec2) -> bool {
+    if self.on_boundary(point) {
+      return true;
+    }
+
+    // Count edges crossed by a ray running off to the right of the point
+    self
+      .edges()
+      .filter(|(a, b)| (a.y > point.y) != (b.y > point.y))
+      .filter(|(a, b)| {
+        // x where the edge crosses the ray's row, compared without dividing
+        let lhs = (point.x - a.x) * (b.y - a.y);
+        let rhs = (b.x - a.x) * (point.y - a.y);
+        match b.y > a.y {
+          true => lhs < rhs,
+          false => lhs > rhs,
+        }
+      })
+      .count()
+      % 2
+      == 1
+  }
+
+  // Helpers
+  fn edges(&self) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+    self
+      .vertices
+      .iter()
+      .zip(self.vertices.iter().cycle().skip(1))
+      .map(|(a, b)| (*a, *b))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn rectangle_counts() {
+    let square = Polygon::from_steps(
+      Vec2::ZERO,
+      [
+        (Direction::East, 4),
+        (Direction::South, 3),
+        (Direction::West, 4),
+        (Direction::North, 3),
+      ],
+    );
+    assert_eq!(square.vertices().len(), 4);
+    assert_eq!(square.double_area(), 24);
+    assert_eq!(square.boundary_points(), 14);
+    assert_eq!(square.interior_points(), 6);
+    assert_eq!(square.lattice_points(), 20);
+  }
+
+  #[test]
+  fn picks_theorem_on_a_triangle() {
+    let triangle = Polygon::new(vec![Vec2::new(0, 0), Vec2::new(4, 0), Vec2::new(0, 2)]);
+    assert_eq!(triangle.area(), 4.0);
+    assert_eq!(triangle.boundary_points(), 8);
+    assert_eq!(triangle.interior_points(), 1);
+
+    let back_and_forth = Polygon::from_steps(Vec2::ZERO, [(Direction::East, 4), (Direction::West, 4)]);
+    assert_eq!(back_and_forth.double_area(), 0);
+    assert_eq!(back_and_forth.interior_points(), 0);
+  }
+
+  #[test]
+  fn point_in_polygon() {
+    // An L shape, so one corner of its bounding box is outside
+    let l_shape = Polygon::new(vec![
+      Vec2::new(0, 0),
+      Vec2::new(2, 0),
+      Vec2::new(2, 2),
+      Vec2::new(4, 2),
+      Vec2::new(4, 4),
+      Vec2::new(0, 4),
+    ]);
+    assert!(l_shape.contains(Vec2::new(1, 1)));
+    assert!(l_shape.contains(Vec2::new(3, 3)));
+    assert!(!l_shape.contains(Vec2::new(3, 1)));
+    assert!(l_shape.on_boundary(Vec2::new(2, 1)));
+    assert!(!l_shape.on_boundary(Vec2::new(1, 1)));
+    assert!(!l_shape.contains(Vec2::new(5, 3)));
+  }
+}
@@ -0,0 +1,242 @@
+#![allow(dead_code)]
+
+use std::{
+  fmt,
+  ops::{Add, Mul, Neg, Sub},
+};
+
+use super::math;
+
+/// Repeatedly takes differences between neighbouring values until a row is
+/// all zeroes (or a single value is left). The first row is `values`.
+pub fn difference_table(values: &[i128]) -> Vec<Vec<i128>> {
+  let mut table = vec![values.to_vec()];
+  loop {
+    let last = table.last().unwrap();
+    if last.len() <= 1 || last.iter().all(|&n| n == 0) {
+      break;
+    }
+    let next = last.windows(2).map(|w| w[1] - w[0]).collect();
+    table.push(next);
+  }
+  table
+}
+
+/// The value `k` places past the end of `values` if the sequence keeps
+/// following its difference table. A negative `k` counts back from the
+/// start instead, so `extrapolate(values, -1)` is the value just before
+/// the first one. `extrapolate(values, 0)` is the last value.
+///
+/// Uses Newton's forward differences, so this doesn't grow the table for
+/// large `k`.
+pub fn extrapolate(values: &[i128], k: i128) -> i128 {
+  assert!(!values.is_empty(), "Can't extrapolate an empty sequence");
+
+  // Index relative to the first value
+  let n = if k < 0 { k } else { values.len() as i128 - 1 + k };
+  let mut binomial = 1;
+  difference_table(values)
+    .iter()
+    .enumerate()
+    .map(|(j, row)| {
+      if j > 0 {
+        // C(n, j) from C(n, j - 1), which always divides exactly
+        binomial = binomial * (n - j as i128 + 1) / j as i128;
+      }
+      binomial * row[0]
+    })
+    .sum()
+}
+
+pub fn next_value(values: &[i128]) -> i128 {
+  extrapolate(values, 1)
+}
+
+pub fn previous_value(values: &[i128]) -> i128 {
+  extrapolate(values, -1)
+}
+
+/// An exact fraction, always stored in lowest terms with a positive
+/// denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+  num: i128,
+  den: i128,
+}
+
+impl Rational {
+  pub const ZERO: Rational = Rational { num: 0, den: 1 };
+
+  pub fn new(num: i128, den: i128) -> Rational {
+    assert!(den != 0, "Rational with a zero denominator");
+    let g = math::gcd(num, den) * den.signum();
+    Rational { num: num / g, den: den / g }
+  }
+
+  pub fn numerator(&self) -> i128 {
+    self.num
+  }
+
+  pub fn denominator(&self) -> i128 {
+    self.den
+  }
+
+  /// The value as an integer, if it is one.
+  pub fn to_integer(self) -> Option<i128> {
+    (self.den == 1).then_some(self.num)
+  }
+}
+
+impl From<i128> for Rational {
+  fn from(n: i128) -> Rational {
+    Rational { num: n, den: 1 }
+  }
+}
+
+impl Add for Rational {
+  type Output = Rational;
+  fn add(self, other: Rational) -> Rational {
+    Rational::new(self.num * other.den + other.num * self.den, self.den * other.den)
+  }
+}
+
+impl Sub for Rational {
+  type Output = Rational;
+  fn sub(self, other: Rational) -> Rational {
+    self + -other
+  }
+}
+
+impl Neg for Rational {
+  type Output = Rational;
+  fn neg(self) -> Rational {
+    Rational { num: -self.num, den: self.den }
+  }
+}
+
+impl Mul for Rational {
+  type Output = Rational;
+  fn mul(self, other: Rational) -> Rational {
+    Rational::new(self.num * other.num, self.den * other.den)
+  }
+}
+
+impl fmt::Display for Rational {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self.den {
+      1 => write!(f, "{}", self.num),
+      _ => write!(f, "{}/{}", self.num, self.den),
+    }
+  }
+}
+
+/// A polynomial with exact rational coefficients, lowest power first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial {
+  coefficients: Vec<Rational>,
+}
+
+impl Polynomial {
+  pub fn new(coefficients: Vec<Rational>) -> Polynomial {
+    let mut poly = Polynomial { coefficients };
+    poly.trim();
+    poly
+  }
+
+  /// The lowest degree polynomial passing through every `(x, y)` point, by
+  /// Lagrange interpolation. Panics if two points share an `x`.
+  pub fn fit(points: &[(i128, i128)]) -> Polynomial {
+    let mut coefficients = vec![Rational::ZERO; points.len()];
+    for (i, &(xi, yi)) in points.iter().enumerate() {
+      // Expand the product of (x - xj) over every other point
+      let mut basis = vec![1_i128];
+      let mut denominator = 1;
+      for (j, &(xj, _)) in points.iter().enumerate() {
+        if i == j {
+          continue;
+        }
+        assert!(xi != xj, "Can't fit a polynomial through two points at x = {}", xi);
+        let mut next = vec![0; basis.len() + 1];
+        for (power, &c) in basis.iter().enumerate() {
+          next[power + 1] += c;
+          next[power] -= c * xj;
+        }
+        basis = next;
+        denominator *= xi - xj;
+      }
+
+      for (power, c) in basis.into_iter().enumerate() {
+        coefficients[power] = coefficients[power] + Rational::new(c * yi, denominator);
+      }
+    }
+    Polynomial::new(coefficients)
+  }
+
+  pub fn coefficients(&self) -> &[Rational] {
+    &self.coefficients
+  }
+
+  /// Degree of the polynomial, counting the zero polynomial as degree 0.
+  pub fn degree(&self) -> usize {
+    self.coefficients.len().saturating_sub(1)
+  }
+
+  pub fn eval(&self, x: i128) -> Rational {
+    let x = Rational::from(x);
+    self
+      .coefficients
+      .iter()
+      .rev()
+      .fold(Rational::ZERO, |acc, &c| acc * x + c)
+  }
+
+  /// Evaluates at `x`, panicking if the result isn't a whole number.
+  pub fn eval_integer(&self, x: i128) -> i128 {
+    let value = self.eval(x);
+    value
+      .to_integer()
+      .unwrap_or_else(|| panic!("Polynomial at {} is {}, not an integer", x, value))
+  }
+
+  // Helpers
+  fn trim(&mut self) {
+    while self.coefficients.last() == Some(&Rational::ZERO) {
+      self.coefficients.pop();
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn extrapolates_sequences() {
+    let table = difference_table(&[1, 3, 6, 10, 15, 21]);
+    assert_eq!(table.len(), 4);
+    assert_eq!(table[2], vec![1, 1, 1, 1]);
+
+    assert_eq!(next_value(&[0, 3, 6, 9, 12, 15]), 18);
+    assert_eq!(next_value(&[10, 13, 16, 21, 30, 45]), 68);
+    assert_eq!(previous_value(&[10, 13, 16, 21, 30, 45]), 5);
+    assert_eq!(extrapolate(&[10, 13, 16, 21, 30, 45], -2), -4);
+    assert_eq!(extrapolate(&[1, 4, 9], -3), 4);
+    assert_eq!(extrapolate(&[1, 4, 9], 7), 100);
+    assert_eq!(extrapolate(&[5], 1000), 5);
+  }
+
+  #[test]
+  fn fits_polynomials() {
+    // 2x^2 - 3x + 1
+    let quadratic = Polynomial::fit(&[(0, 1), (1, 0), (2, 3)]);
+    assert_eq!(quadratic.degree(), 2);
+    assert_eq!(quadratic.coefficients()[1], Rational::from(-3));
+    assert_eq!(quadratic.eval_integer(100), 19701);
+
+    // A line through points that needs half-unit coefficients
+    let line = Polynomial::fit(&[(0, 0), (2, 1), (4, 2)]);
+    assert_eq!(line.degree(), 1);
+    assert_eq!(line.eval(3), Rational::new(3, 2));
+    assert_eq!(line.eval(3).to_string(), "3/2");
+  }
+}
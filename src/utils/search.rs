@@ -0,0 +1,236 @@
+#![allow(dead_code)]
+
+use std::{
+  cmp::Reverse,
+  collections::{BinaryHeap, HashMap, VecDeque},
+  hash::Hash,
+};
+
+/// A route found by one of the searches, from a start state to the goal
+/// state inclusive. For BFS the cost is the number of steps taken.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S> {
+  pub cost: usize,
+  pub states: Vec<S>,
+}
+
+impl<S> Path<S> {
+  pub fn start(&self) -> &S {
+    &self.states[0]
+  }
+
+  pub fn goal(&self) -> &S {
+    &self.states[self.states.len() - 1]
+  }
+}
+
+/// Breadth-first search over unweighted edges. Every state in `starts` is
+/// searched from at once, and the search stops at the first state that
+/// satisfies `is_goal`.
+pub fn bfs<S, I>(
+  starts: impl IntoIterator<Item = S>,
+  mut neighbours: impl FnMut(&S) -> I,
+  mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+  S: Clone + Eq + Hash,
+  I: IntoIterator<Item = S>,
+{
+  let mut explored = Explored::new();
+  let mut queue: VecDeque<usize> = VecDeque::new();
+  for start in starts {
+    if let Some(node) = explored.insert(start, None, 0) {
+      queue.push_back(node);
+    }
+  }
+
+  while let Some(node) = queue.pop_front() {
+    if is_goal(&explored.nodes[node].state) {
+      return Some(explored.path_to(node));
+    }
+
+    let cost = explored.nodes[node].cost + 1;
+    for next in neighbours(&explored.nodes[node].state) {
+      if let Some(next_node) = explored.insert(next, Some(node), cost) {
+        queue.push_back(next_node);
+      }
+    }
+  }
+
+  None
+}
+
+/// Cheapest-first search where `neighbours` yields each reachable state
+/// along with the cost of the edge to it.
+pub fn dijkstra<S, I>(
+  starts: impl IntoIterator<Item = S>,
+  neighbours: impl FnMut(&S) -> I,
+  is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+  S: Clone + Eq + Hash,
+  I: IntoIterator<Item = (S, usize)>,
+{
+  astar(starts, neighbours, |_| 0, is_goal)
+}
+
+/// Dijkstra guided by `heuristic`, an estimate of the remaining cost to a
+/// goal. The heuristic must never overestimate or the path found may not
+/// be the cheapest.
+pub fn astar<S, I>(
+  starts: impl IntoIterator<Item = S>,
+  mut neighbours: impl FnMut(&S) -> I,
+  mut heuristic: impl FnMut(&S) -> usize,
+  mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+  S: Clone + Eq + Hash,
+  I: IntoIterator<Item = (S, usize)>,
+{
+  let mut explored = Explored::new();
+  let mut heap: BinaryHeap<Reverse<(usize, usize, usize)>> = BinaryHeap::new();
+  for start in starts {
+    let estimate = heuristic(&start);
+    if let Some(node) = explored.insert(start, None, 0) {
+      heap.push(Reverse((estimate, 0, node)));
+    }
+  }
+
+  while let Some(Reverse((_, cost, node))) = heap.pop() {
+    // A cheaper route to this state was found after it was queued
+    if cost > explored.nodes[node].cost {
+      continue;
+    }
+    if is_goal(&explored.nodes[node].state) {
+      return Some(explored.path_to(node));
+    }
+
+    for (next, step_cost) in neighbours(&explored.nodes[node].state) {
+      let next_cost = cost + step_cost;
+      let estimate = next_cost + heuristic(&next);
+      if let Some(next_node) = explored.relax(next, node, next_cost) {
+        heap.push(Reverse((estimate, next_cost, next_node)));
+      }
+    }
+  }
+
+  None
+}
+
+// Helpers
+struct Node<S> {
+  state: S,
+  parent: Option<usize>,
+  cost: usize,
+}
+
+/// Every state seen so far, stored once with a back-link to the node it
+/// was reached from so the path can be rebuilt at the end.
+struct Explored<S> {
+  nodes: Vec<Node<S>>,
+  index: HashMap<S, usize>,
+}
+
+impl<S: Clone + Eq + Hash> Explored<S> {
+  fn new() -> Explored<S> {
+    Explored {
+      nodes: Vec::new(),
+      index: HashMap::new(),
+    }
+  }
+
+  // Adds a state that hasn't been seen before, returning its node
+  fn insert(&mut self, state: S, parent: Option<usize>, cost: usize) -> Option<usize> {
+    if self.index.contains_key(&state) {
+      return None;
+    }
+    let node = self.nodes.len();
+    self.index.insert(state.clone(), node);
+    self.nodes.push(Node { state, parent, cost });
+    Some(node)
+  }
+
+  // Records a route to `state` if it's new or cheaper than the best so far
+  fn relax(&mut self, state: S, parent: usize, cost: usize) -> Option<usize> {
+    match self.index.get(&state) {
+      Some(&node) if self.nodes[node].cost <= cost => None,
+      Some(&node) => {
+        self.nodes[node].cost = cost;
+        self.nodes[node].parent = Some(parent);
+        Some(node)
+      }
+      None => self.insert(state, Some(parent), cost),
+    }
+  }
+
+  fn path_to(&self, goal: usize) -> Path<S> {
+    let mut states = Vec::new();
+    let mut current = Some(goal);
+    while let Some(node) = current {
+      states.push(self.nodes[node].state.clone());
+      current = self.nodes[node].parent;
+    }
+    states.reverse();
+
+    Path {
+      cost: self.nodes[goal].cost,
+      states,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::utils::geometry::Vec2;
+  use crate::utils::grid::Grid;
+
+  fn maze() -> Grid<char> {
+    Grid::parse(&["S..#....", ".#.#.##.", ".#...#..", ".####.#.", "......#E"])
+  }
+
+  fn open_neighbours(maze: &Grid<char>, pos: Vec2) -> Vec<Vec2> {
+    pos.neighbours4().filter(|p| maze.get(*p).is_some_and(|c| *c != '#')).collect()
+  }
+
+  #[test]
+  fn bfs_finds_shortest_route() {
+    let maze = maze();
+    let start = Vec2::from(maze.find(&'S').unwrap());
+    let end = Vec2::from(maze.find(&'E').unwrap());
+
+    let path = bfs([start], |p| open_neighbours(&maze, *p), |p| *p == end).unwrap();
+    assert_eq!(path.cost, 15);
+    assert_eq!(path.states.len(), 16);
+    assert_eq!((*path.start(), *path.goal()), (start, end));
+    assert!(path.states.windows(2).all(|w| w[0].manhattan(w[1]) == 1));
+
+    assert_eq!(bfs([start], |p| open_neighbours(&maze, *p), |_| false), None);
+  }
+
+  #[test]
+  fn multi_source_starts_from_nearest() {
+    let line: Vec<usize> = (0..10).collect();
+    let path = bfs([0, 6], |n| [n + 1].into_iter().filter(|n| line.contains(n)), |n| *n == 9).unwrap();
+    assert_eq!(path.cost, 3);
+    assert_eq!(path.states, vec![6, 7, 8, 9]);
+  }
+
+  #[test]
+  fn weighted_searches_agree() {
+    // Going the long way round 0 -> 1 -> 2 -> 3 is cheaper than the direct hop
+    let edges = |n: &u32| match n {
+      0 => vec![(1, 1), (3, 10)],
+      1 => vec![(2, 2)],
+      2 => vec![(3, 3)],
+      _ => vec![],
+    };
+
+    let cheapest = dijkstra([0], edges, |n| *n == 3).unwrap();
+    assert_eq!(cheapest.cost, 6);
+    assert_eq!(cheapest.states, vec![0, 1, 2, 3]);
+
+    let guided = astar([0], edges, |n| (3 - *n) as usize, |n| *n == 3).unwrap();
+    assert_eq!(guided, cheapest);
+  }
+}
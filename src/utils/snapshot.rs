@@ -0,0 +1,127 @@
+#![allow(dead_code)]
+
+use std::{env, fs, path::Path};
+
+use itertools::Itertools;
+
+/// Set this env var (to anything) to write the actual output over the
+/// stored snapshot instead of failing, e.g. `BLESS_SNAPSHOTS=1 cargo test`.
+pub const BLESS_VAR: &str = "BLESS_SNAPSHOTS";
+
+/// Renders a grid to text, one line per row, using `to_char` for each cell.
+pub fn render_grid<T>(grid: &[Vec<T>], to_char: impl Fn(&T) -> char) -> String {
+  grid
+    .iter()
+    .map(|row| row.iter().map(&to_char).collect::<String>())
+    .join("\n")
+}
+
+/// Compares `actual` against the snapshot stored next to the calling test in
+/// `snapshots/<name>.snap`. Prefer this over calling `check_snapshot` directly
+/// since it works out the snapshot directory from the caller's file.
+#[cfg(test)]
+macro_rules! assert_snapshot {
+  ($name:expr, $actual:expr) => {
+    $crate::utils::snapshot::check_snapshot(
+      &std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join(file!())
+        .parent()
+        .unwrap()
+        .join("snapshots")
+        .join(format!("{}.snap", $name)),
+      &$actual,
+    )
+  };
+}
+#[cfg(test)]
+pub(crate) use assert_snapshot;
+
+/// Checks `actual` against the snapshot file at `path`, panicking with a
+/// line diff if they differ. With `BLESS_SNAPSHOTS` set the file is
+/// (re)written instead, which is also how new snapshots get created.
+pub fn check_snapshot(path: &Path, actual: &str) {
+  let actual = normalize(actual);
+
+  if env::var_os(BLESS_VAR).is_some() {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, &actual).unwrap_or_else(|e| panic!("Unable to write snapshot {:?}: {}", path, e));
+    return;
+  }
+
+  let expected = match fs::read_to_string(path) {
+    Ok(contents) => normalize(&contents),
+    Err(_) => panic!(
+      "Missing snapshot {:?}, rerun with {}=1 to create it. Actual output:\n{}",
+      path, BLESS_VAR, actual
+    ),
+  };
+
+  if expected != actual {
+    panic!(
+      "Snapshot {:?} does not match (- expected, + actual), rerun with {}=1 to accept:\n{}",
+      path,
+      BLESS_VAR,
+      line_diff(&expected, &actual)
+    );
+  }
+}
+
+// Helpers
+fn normalize(text: &str) -> String {
+  let mut text = text.replace("\r\n", "\n").trim_end().to_owned();
+  text.push('\n');
+  text
+}
+
+/// Builds a unified-style diff of two texts via the longest common
+/// subsequence of their lines.
+fn line_diff(expected: &str, actual: &str) -> String {
+  let old = expected.lines().collect_vec();
+  let new = actual.lines().collect_vec();
+
+  // lcs[i][j] is the LCS length of old[i..] and new[j..]
+  let mut lcs = vec![vec![0; new.len() + 1]; old.len() + 1];
+  for i in (0..old.len()).rev() {
+    for j in (0..new.len()).rev() {
+      lcs[i][j] = if old[i] == new[j] {
+        lcs[i + 1][j + 1] + 1
+      } else {
+        lcs[i + 1][j].max(lcs[i][j + 1])
+      };
+    }
+  }
+
+  let mut out = Vec::new();
+  let (mut i, mut j) = (0, 0);
+  while i < old.len() || j < new.len() {
+    if i < old.len() && j < new.len() && old[i] == new[j] {
+      out.push(format!("  {}", old[i]));
+      i += 1;
+      j += 1;
+    } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+      out.push(format!("- {}", old[i]));
+      i += 1;
+    } else {
+      out.push(format!("+ {}", new[j]));
+      j += 1;
+    }
+  }
+  out.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn renders_grid_rows() {
+    let grid = vec![vec![true, false], vec![false, true]];
+    assert_eq!(render_grid(&grid, |b| if *b { '#' } else { '.' }), "#.\n.#");
+  }
+
+  #[test]
+  fn diff_marks_changed_lines() {
+    let diff = line_diff("a\nb\nc", "a\nx\nc");
+    assert_eq!(diff, "  a\n- b\n+ x\n  c");
+  }
+}
@@ -0,0 +1,161 @@
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use super::{geometry::Vec2, grid::Grid};
+
+/// An unbounded 2D map that only stores the cells that have been written.
+/// Reading anywhere else gives the default value, so it suits puzzles
+/// where things wander off in every direction (rope knots, dig sites).
+///
+/// The bounding box grows to cover every cell ever inserted and never
+/// shrinks, even after a `remove`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+  cells: HashMap<Vec2, T>,
+  default: T,
+  bounds: Option<(Vec2, Vec2)>,
+}
+
+impl<T> SparseGrid<T> {
+  /// An empty grid where every unset cell reads as `default`.
+  pub fn new(default: T) -> SparseGrid<T> {
+    SparseGrid {
+      cells: HashMap::new(),
+      default,
+      bounds: None,
+    }
+  }
+
+  /// The value at `pos`, or the default if nothing's been put there.
+  pub fn get(&self, pos: Vec2) -> &T {
+    self.cells.get(&pos).unwrap_or(&self.default)
+  }
+
+  /// Whether `pos` has been explicitly set.
+  pub fn contains(&self, pos: Vec2) -> bool {
+    self.cells.contains_key(&pos)
+  }
+
+  /// Sets a cell, returning whatever was set there before.
+  pub fn insert(&mut self, pos: Vec2, value: T) -> Option<T> {
+    self.bounds = Some(match self.bounds {
+      Some((min, max)) => (
+        Vec2::new(min.x.min(pos.x), min.y.min(pos.y)),
+        Vec2::new(max.x.max(pos.x), max.y.max(pos.y)),
+      ),
+      None => (pos, pos),
+    });
+    self.cells.insert(pos, value)
+  }
+
+  pub fn remove(&mut self, pos: Vec2) -> Option<T> {
+    self.cells.remove(&pos)
+  }
+
+  /// Number of cells that have been set.
+  pub fn len(&self) -> usize {
+    self.cells.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.cells.is_empty()
+  }
+
+  /// Every set cell, in no particular order.
+  pub fn iter(&self) -> impl Iterator<Item = (Vec2, &T)> {
+    self.cells.iter().map(|(pos, value)| (*pos, value))
+  }
+
+  /// The smallest and largest corners (inclusive) of the box around every
+  /// cell ever set, or `None` if nothing has been.
+  pub fn bounds(&self) -> Option<(Vec2, Vec2)> {
+    self.bounds
+  }
+
+  /// The four orthogonal neighbours of `pos` with their values. There's no
+  /// edge, so there are always four.
+  pub fn neighbours4(&self, pos: Vec2) -> impl Iterator<Item = (Vec2, &T)> {
+    pos.neighbours4().map(move |n| (n, self.get(n)))
+  }
+
+  /// All eight neighbours of `pos`, diagonals included, with their values.
+  pub fn neighbours8(&self, pos: Vec2) -> impl Iterator<Item = (Vec2, &T)> {
+    (-1..=1)
+      .flat_map(|y| (-1..=1).map(move |x| Vec2::new(x, y)))
+      .filter(|offset| *offset != Vec2::ZERO)
+      .map(move |offset| (pos + offset, self.get(pos + offset)))
+  }
+
+  /// Copies the bounding box out into a dense grid, with the box's top
+  /// left corner at (0, 0). Empty if nothing has been set.
+  pub fn to_grid(&self) -> Grid<T>
+  where
+    T: Clone,
+  {
+    let Some((min, max)) = self.bounds else {
+      return Grid::from_rows(Vec::new());
+    };
+    let rows = (min.y..=max.y)
+      .map(|y| (min.x..=max.x).map(|x| self.get(Vec2::new(x, y)).clone()).collect())
+      .collect();
+    Grid::from_rows(rows)
+  }
+
+  /// Draws the bounding box one character per cell, rows joined by
+  /// newlines. Unset cells are drawn from the default value.
+  pub fn render(&self, to_char: impl Fn(&T) -> char) -> String {
+    let Some((min, max)) = self.bounds else {
+      return String::new();
+    };
+    (min.y..=max.y)
+      .map(|y| (min.x..=max.x).map(|x| to_char(self.get(Vec2::new(x, y)))).collect::<String>())
+      .collect::<Vec<_>>()
+      .join("\n")
+  }
+}
+
+impl<T: Default> Default for SparseGrid<T> {
+  fn default() -> SparseGrid<T> {
+    SparseGrid::new(T::default())
+  }
+}
+
+impl<T: Default> FromIterator<(Vec2, T)> for SparseGrid<T> {
+  fn from_iter<I: IntoIterator<Item = (Vec2, T)>>(iter: I) -> SparseGrid<T> {
+    let mut grid = SparseGrid::default();
+    for (pos, value) in iter {
+      grid.insert(pos, value);
+    }
+    grid
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn tracks_bounds_and_defaults() {
+    let mut grid = SparseGrid::new('.');
+    assert_eq!(grid.bounds(), None);
+    assert_eq!(grid.render(|c| *c), "");
+
+    grid.insert(Vec2::new(-2, 1), '#');
+    grid.insert(Vec2::new(1, -1), '#');
+    assert_eq!(grid.insert(Vec2::new(1, -1), 'O'), Some('#'));
+    assert_eq!(grid.get(Vec2::new(0, 0)), &'.');
+    assert_eq!(grid.len(), 2);
+    assert_eq!(grid.bounds(), Some((Vec2::new(-2, -1), Vec2::new(1, 1))));
+    assert_eq!(grid.render(|c| *c), "...O\n....\n#...");
+    assert_eq!(grid.to_grid().to_string(), grid.render(|c| *c));
+  }
+
+  #[test]
+  fn neighbours_see_defaults() {
+    let grid: SparseGrid<u32> = [(Vec2::new(0, 0), 5), (Vec2::new(1, 1), 2)].into_iter().collect();
+    assert_eq!(grid.neighbours4(Vec2::new(1, 0)).map(|(_, v)| *v).sum::<u32>(), 7);
+    assert_eq!(grid.neighbours8(Vec2::new(1, 0)).count(), 8);
+    assert_eq!(grid.neighbours8(Vec2::new(0, 1)).map(|(_, v)| *v).sum::<u32>(), 7);
+  }
+}
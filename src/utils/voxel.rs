@@ -0,0 +1,182 @@
+#![allow(dead_code)]
+
+use std::collections::{HashSet, VecDeque};
+
+use super::geometry::Vec3;
+
+/// An axis-aligned box of whole cells. Both corners are inclusive, which
+/// is how AoC writes ranges like `x=10..12`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cuboid {
+  pub min: Vec3,
+  pub max: Vec3,
+}
+
+impl Cuboid {
+  /// The box spanning both corners, whichever way round they're given.
+  pub fn new(a: Vec3, b: Vec3) -> Cuboid {
+    Cuboid {
+      min: a.min(b),
+      max: a.max(b),
+    }
+  }
+
+  /// Number of cells inside.
+  pub fn volume(&self) -> u64 {
+    let size = self.max - self.min + Vec3::new(1, 1, 1);
+    size.x as u64 * size.y as u64 * size.z as u64
+  }
+
+  pub fn contains(&self, point: Vec3) -> bool {
+    self.min.min(point) == self.min && self.max.max(point) == self.max
+  }
+
+  /// The cells both boxes cover, if there are any.
+  pub fn intersect(&self, other: &Cuboid) -> Option<Cuboid> {
+    let min = self.min.max(other.min);
+    let max = self.max.min(other.max);
+    (min.x <= max.x && min.y <= max.y && min.z <= max.z).then_some(Cuboid { min, max })
+  }
+
+  /// Grown by `amount` cells on every side.
+  pub fn expand(&self, amount: isize) -> Cuboid {
+    let pad = Vec3::new(amount, amount, amount);
+    Cuboid {
+      min: self.min - pad,
+      max: self.max + pad,
+    }
+  }
+
+  pub fn points(&self) -> impl Iterator<Item = Vec3> + '_ {
+    (self.min.x..=self.max.x).flat_map(move |x| {
+      (self.min.y..=self.max.y).flat_map(move |y| (self.min.z..=self.max.z).map(move |z| Vec3::new(x, y, z)))
+    })
+  }
+}
+
+/// A set of unit cubes, e.g. the lava droplet scan from 2022 day 18.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VoxelSet {
+  voxels: HashSet<Vec3>,
+}
+
+impl VoxelSet {
+  pub fn new() -> VoxelSet {
+    VoxelSet::default()
+  }
+
+  pub fn insert(&mut self, voxel: Vec3) -> bool {
+    self.voxels.insert(voxel)
+  }
+
+  pub fn contains(&self, voxel: Vec3) -> bool {
+    self.voxels.contains(&voxel)
+  }
+
+  pub fn len(&self) -> usize {
+    self.voxels.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.voxels.is_empty()
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = Vec3> + '_ {
+    self.voxels.iter().copied()
+  }
+
+  /// The smallest box holding every voxel, or `None` if there aren't any.
+  pub fn bounds(&self) -> Option<Cuboid> {
+    let mut voxels = self.iter();
+    let first = voxels.next()?;
+    let (min, max) = voxels.fold((first, first), |(min, max), v| (min.min(v), max.max(v)));
+    Some(Cuboid { min, max })
+  }
+
+  /// The neighbours of `voxel` that are in the set.
+  pub fn neighbours6(&self, voxel: Vec3) -> impl Iterator<Item = Vec3> + '_ {
+    voxel.neighbours6().filter(|n| self.contains(*n))
+  }
+
+  /// Faces not touching another voxel, counting faces on internal air
+  /// pockets too.
+  pub fn surface_area(&self) -> usize {
+    self
+      .iter()
+      .flat_map(|v| v.neighbours6())
+      .filter(|n| !self.contains(*n))
+      .count()
+  }
+
+  /// Every empty cell reachable from outside without passing through a
+  /// voxel, within a box one cell bigger than the set on each side.
+  pub fn exterior(&self) -> VoxelSet {
+    let Some(bounds) = self.bounds().map(|b| b.expand(1)) else {
+      return VoxelSet::new();
+    };
+
+    // The expanded box's corner is always outside, so flood from there
+    let mut outside = VoxelSet::new();
+    let mut queue = VecDeque::from([bounds.min]);
+    outside.insert(bounds.min);
+    while let Some(current) = queue.pop_front() {
+      for next in current.neighbours6() {
+        if bounds.contains(next) && !self.contains(next) && outside.insert(next) {
+          queue.push_back(next);
+        }
+      }
+    }
+    outside
+  }
+
+  /// Faces that can be reached from outside, leaving out trapped pockets.
+  pub fn exterior_surface_area(&self) -> usize {
+    let outside = self.exterior();
+    self
+      .iter()
+      .flat_map(|v| v.neighbours6())
+      .filter(|n| outside.contains(*n))
+      .count()
+  }
+}
+
+impl FromIterator<Vec3> for VoxelSet {
+  fn from_iter<I: IntoIterator<Item = Vec3>>(iter: I) -> VoxelSet {
+    VoxelSet {
+      voxels: iter.into_iter().collect(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn cuboid_volume_and_intersection() {
+    let a = Cuboid::new(Vec3::new(10, 10, 10), Vec3::new(12, 12, 12));
+    let b = Cuboid::new(Vec3::new(13, 13, 13), Vec3::new(11, 11, 11));
+    assert_eq!(a.volume(), 27);
+    assert_eq!(a.intersect(&b), Some(Cuboid::new(Vec3::new(11, 11, 11), Vec3::new(12, 12, 12))));
+    assert_eq!(a.intersect(&b).unwrap().volume(), 8);
+    assert_eq!(a.intersect(&Cuboid::new(Vec3::new(13, 0, 0), Vec3::new(20, 20, 20))), None);
+    assert!(a.contains(Vec3::new(12, 10, 11)));
+    assert_eq!(a.points().count(), 27);
+  }
+
+  #[test]
+  fn droplet_surface_areas() {
+    // The 2022 day 18 example, which has a single trapped air cell
+    let droplet: VoxelSet = [
+      "2,2,2", "1,2,2", "3,2,2", "2,1,2", "2,3,2", "2,2,1", "2,2,3", "2,2,4", "2,2,6", "1,2,5", "3,2,5", "2,1,5",
+      "2,3,5",
+    ]
+    .iter()
+    .map(|p| p.parse::<Vec3>().unwrap())
+    .collect();
+
+    assert_eq!(droplet.surface_area(), 64);
+    assert_eq!(droplet.exterior_surface_area(), 58);
+    assert_eq!(droplet.neighbours6(Vec3::new(2, 2, 2)).count(), 6);
+  }
+}